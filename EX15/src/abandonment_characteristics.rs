@@ -0,0 +1,161 @@
+use std::error::Error;
+use crate::queuing_system::QueuingSystem;
+
+/// Характеристики СМО с нетерпеливыми заявками (Erlang-A).
+/// Вычисляются по стационарному распределению уравнений Колмогорова,
+/// в которых заявка в очереди уходит с интенсивностью θ.
+pub trait AbandonmentCharacteristics {
+    fn calculate_abandonment_probability(&self) -> Result<f64, Box<dyn Error>>;
+    fn calculate_effective_throughput(&self) -> Result<f64, Box<dyn Error>>;
+    fn calculate_average_waiting_time_of_served(&self) -> Result<f64, Box<dyn Error>>;
+    fn calculate_average_waiting_time_of_abandoned(&self) -> Result<f64, Box<dyn Error>>;
+}
+
+impl QueuingSystem {
+    /// Для заявки, перед которой в очереди стоят `ahead` заявок, вычисляет
+    /// вероятность дождаться обслуживания, а также E[W·1{обслужена}] и E[W·1{ушла}].
+    /// На позиции i (i заявок впереди) очередь продвигается с интенсивностью sμ + iθ,
+    /// а сама заявка уходит с интенсивностью θ.
+    fn tagged_customer_outcome(&self, ahead: usize) -> (f64, f64, f64) {
+        let service_rate = self.num_channels as f64 * self.mu_rate;
+        let theta = self.abandonment_rate;
+        let total_rate = |i: usize| service_rate + (i as f64 + 1.0) * theta;
+
+        // Проходим позиции от `ahead` к голове очереди
+        let (probability_served, time_served, time_abandoned, _) = (0..=ahead).rev().fold(
+            (1.0, 0.0, 0.0, 0.0),
+            |(reach_probability, _, time_abandoned, elapsed), i| {
+                let elapsed = elapsed + 1.0 / total_rate(i);
+                let abandoned_here = reach_probability * theta / total_rate(i);
+                let advance_probability = (service_rate + i as f64 * theta) / total_rate(i);
+                let reach_next = reach_probability * advance_probability;
+                (reach_next, reach_next * elapsed, time_abandoned + abandoned_here * elapsed, elapsed)
+            },
+        );
+
+        (probability_served, time_served, time_abandoned)
+    }
+
+    /// Суммирует исходы по распределению состояний, которые застают присоединившиеся заявки.
+    fn joined_customer_outcomes(&self) -> Result<(f64, f64, f64, f64), Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let num_channels = self.num_channels as usize;
+        let queue_max_index = distribution.len() - 1;
+
+        Ok((0..queue_max_index).fold((0.0, 0.0, 0.0, 0.0), |(served, abandoned, time_served, time_abandoned), i| {
+            let p = distribution[i];
+            if i < num_channels {
+                (served + p, abandoned, time_served, time_abandoned)
+            } else {
+                let (probability_served, t_served, t_abandoned) = self.tagged_customer_outcome(i - num_channels);
+                (
+                    served + p * probability_served,
+                    abandoned + p * (1.0 - probability_served),
+                    time_served + p * t_served,
+                    time_abandoned + p * t_abandoned,
+                )
+            }
+        }))
+    }
+}
+
+impl AbandonmentCharacteristics for QueuingSystem {
+    /// Вычисляет вероятность того, что поступившая заявка покинет очередь, не дождавшись обслуживания.
+    /// # Возвращаемое значение
+    /// Отношение интенсивности уходов из очереди к интенсивности входящего потока, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_abandonment_probability(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let num_channels = self.num_channels as usize;
+
+        let abandonment_intensity: f64 = distribution.iter()
+            .enumerate()
+            .skip(num_channels)
+            .map(|(i, p)| (i - num_channels) as f64 * self.abandonment_rate * p)
+            .sum();
+
+        Ok(abandonment_intensity / self.lambda_rate)
+    }
+
+    /// Вычисляет эффективную пропускную способность (интенсивность потока обслуженных заявок).
+    /// # Возвращаемое значение
+    /// Интенсивность завершения обслуживания Σ min(i, s)·μ·p_i, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_effective_throughput(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let num_channels = self.num_channels as usize;
+
+        Ok(distribution.iter()
+            .enumerate()
+            .map(|(i, p)| i.min(num_channels) as f64 * self.mu_rate * p)
+            .sum())
+    }
+
+    /// Вычисляет среднее время ожидания в очереди для заявок, дождавшихся обслуживания.
+    /// # Возвращаемое значение
+    /// Условное среднее время ожидания обслуженной заявки, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_average_waiting_time_of_served(&self) -> Result<f64, Box<dyn Error>> {
+        let (served, _, time_served, _) = self.joined_customer_outcomes()?;
+        Ok(if served > 0.0 { time_served / served } else { 0.0 })
+    }
+
+    /// Вычисляет среднее время, проведённое в очереди заявками, покинувшими её без обслуживания.
+    /// # Возвращаемое значение
+    /// Условное среднее время до ухода из очереди, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_average_waiting_time_of_abandoned(&self) -> Result<f64, Box<dyn Error>> {
+        let (_, abandoned, _, time_abandoned) = self.joined_customer_outcomes()?;
+        Ok(if abandoned > 0.0 { time_abandoned / abandoned } else { 0.0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+    fn erlang_a(lambda_rate: f64, mu_rate: f64, abandonment_rate: f64, num_channels: i32, queue_size: i32) -> QueuingSystem {
+        QueuingSystem::new(
+            lambda_rate,
+            mu_rate,
+            abandonment_rate,
+            None,
+            None,
+            num_channels,
+            queue_size,
+            Config::empty_initial_state(num_channels, queue_size),
+            1,
+            100,
+            0.01
+        )
+    }
+
+    #[test]
+    fn single_channel_with_theta_equal_to_mu_is_poisson() {
+        // M/M/1+M при θ = μ: интенсивность ухода из S_i равна iμ, как в M/M/∞,
+        // поэтому число заявок распределено по Пуассону с параметром ρ = λ/μ,
+        // а время пребывания каждой заявки в системе — экспоненциальное со средним 1/μ.
+        let (lambda, mu) = (2.0, 1.0);
+        let system = erlang_a(lambda, mu, mu, 1, 60);
+        let characteristics = system.stationary_characteristics().unwrap();
+        let p0 = (-lambda / mu).exp();
+
+        assert!((characteristics.calculate_probability_of_downtime() - p0).abs() < 1e-9);
+        assert!((characteristics.calculate_total_number_of_requests() - lambda / mu).abs() < 1e-9);
+        assert!((characteristics.calculate_average_number_of_requests_in_queue() - (1.0 + p0)).abs() < 1e-9);
+        assert!((characteristics.calculate_average_time_in_system() - 1.0 / mu).abs() < 1e-9);
+        assert!((characteristics.calculate_average_waiting_time_in_queue() - (1.0 + p0) / lambda).abs() < 1e-9);
+        assert!((system.calculate_abandonment_probability().unwrap() - (1.0 + p0) / 2.0).abs() < 1e-9);
+        assert!((system.calculate_effective_throughput().unwrap() - mu * (1.0 - p0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn admitted_rate_balances_arrivals_not_lost_to_a_full_queue() {
+        // Принятые заявки либо обслуживаются, либо уходят из очереди: X + θ·Lq = λ·(1 - p_{s+n})
+        let system = erlang_a(6.0, 1.5, 0.8, 3, 4);
+        let characteristics = system.stationary_characteristics().unwrap();
+        let full = characteristics.distribution[characteristics.distribution.len() - 1];
+
+        assert!((characteristics.admitted_rate - 6.0 * (1.0 - full)).abs() < 1e-9);
+        // Не обслуживаются заявки, заставшие полную очередь, и ушедшие из очереди
+        assert!((characteristics.calculate_rejection_probability() - full - system.calculate_abandonment_probability().unwrap()).abs() < 1e-9);
+    }
+}
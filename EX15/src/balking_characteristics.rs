@@ -0,0 +1,86 @@
+use std::error::Error;
+use crate::queuing_system::QueuingSystem;
+
+/// Характеристики СМО, в которой заявка может отказаться присоединиться к очереди.
+/// Потери из-за отказа от присоединения учитываются отдельно от отказов при заполненной очереди.
+pub trait BalkingCharacteristics {
+    fn calculate_balking_probability(&self) -> Result<f64, Box<dyn Error>>;
+    fn calculate_full_buffer_rejection_probability(&self) -> Result<f64, Box<dyn Error>>;
+    fn calculate_effective_arrival_rate(&self) -> Result<f64, Box<dyn Error>>;
+}
+
+impl BalkingCharacteristics for QueuingSystem {
+    /// Вычисляет вероятность того, что поступившая заявка откажется присоединиться к очереди.
+    /// # Возвращаемое значение
+    /// Σ p_i·(1 - b(k_i)) по состояниям с незаполненной очередью, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_balking_probability(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let queue_max_index = distribution.len() - 1;
+
+        Ok((0..queue_max_index)
+            .map(|i| distribution[i] * (1.0 - self.join_probability(i)))
+            .sum())
+    }
+
+    /// Вычисляет вероятность отказа из-за заполненной очереди (состояние S_{s+n}).
+    /// # Возвращаемое значение
+    /// Стационарная вероятность последнего состояния, тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_full_buffer_rejection_probability(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        Ok(distribution[distribution.len() - 1])
+    }
+
+    /// Вычисляет интенсивность потока заявок, действительно вошедших в систему.
+    /// # Возвращаемое значение
+    /// λ·(1 - P_отк.присоед - P_отк.очередь), тип: `Result<f64, Box<dyn Error>>`.
+    fn calculate_effective_arrival_rate(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.lambda_rate
+            * (1.0 - self.calculate_balking_probability()? - self.calculate_full_buffer_rejection_probability()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+    fn with_balking(lambda_rate: f64, mu_rate: f64, balking: fn(i32) -> f64, num_channels: i32, queue_size: i32) -> QueuingSystem {
+        QueuingSystem::new(
+            lambda_rate,
+            mu_rate,
+            0.0,
+            Some(balking),
+            None,
+            num_channels,
+            queue_size,
+            Config::empty_initial_state(num_channels, queue_size),
+            1,
+            100,
+            0.01
+        )
+    }
+
+    #[test]
+    fn single_channel_with_discouraged_arrivals_matches_closed_form() {
+        // M/M/1 с b(k) = 1/(k + 1): p_i = p_0·ρⁱ/(i - 1)! при i ≥ 1, откуда p_0 = 1/(1 + ρ·e^ρ)
+        let rho: f64 = 1.5;
+        let system = with_balking(rho, 1.0, |k| 1.0 / (k as f64 + 1.0), 1, 60);
+        let distribution = system.stationary_characteristics().unwrap().distribution;
+        let p0 = 1.0 / (1.0 + rho * rho.exp());
+
+        assert!((distribution[0] - p0).abs() < 1e-9);
+        assert!((distribution[1] - p0 * rho).abs() < 1e-9);
+        assert!((distribution[3] - p0 * rho.powi(3) / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejection_is_balking_plus_full_buffer() {
+        let system = with_balking(4.0, 1.5, |k| 1.0 / (k as f64 + 1.0), 2, 3);
+        let characteristics = system.stationary_characteristics().unwrap();
+        let losses = system.calculate_balking_probability().unwrap() + system.calculate_full_buffer_rejection_probability().unwrap();
+
+        assert!((characteristics.calculate_rejection_probability() - losses).abs() < 1e-9);
+        assert!((system.calculate_effective_arrival_rate().unwrap() - characteristics.admitted_rate).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,48 @@
+use std::error::Error;
+
+/// Правило приёма группы заявок, которая не помещается в систему целиком.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchRejectionPolicy {
+    Partial,    // Принимается часть группы, заполняющая систему, остальные получают отказ
+    WholeBatch, // Группа получает отказ целиком
+}
+
+/// Групповое поступление заявок: λ задаёт интенсивность потока групп.
+#[derive(Debug, Clone)]
+pub struct BatchArrivals {
+    pub size_probabilities: Vec<f64>, // Вероятности размеров группы 1, 2, 3, ...
+    pub policy: BatchRejectionPolicy, // Правило приёма неполностью помещающейся группы
+}
+
+impl BatchArrivals {
+    /// Создаёт распределение размера группы.
+    /// # Параметры
+    /// * `size_probabilities` - Вероятности размеров группы 1, 2, 3, ...: неотрицательные, в сумме 1.
+    /// * `policy` - Правило приёма неполностью помещающейся группы.
+    /// # Возвращаемое значение
+    /// Групповое поступление либо ошибка при некорректных вероятностях, тип: `Result<BatchArrivals, Box<dyn Error>>`.
+    pub fn new(size_probabilities: Vec<f64>, policy: BatchRejectionPolicy) -> Result<BatchArrivals, Box<dyn Error>> {
+        if size_probabilities.is_empty() {
+            return Err("распределение размера группы не задано".into());
+        }
+        if size_probabilities.iter().any(|g| !g.is_finite() || *g < 0.0) {
+            return Err(format!("вероятности размеров группы должны быть неотрицательными: {:?}", size_probabilities).into());
+        }
+        let total: f64 = size_probabilities.iter().sum();
+        if (total - 1.0).abs() > 1e-9 {
+            return Err(format!("вероятности размеров группы в сумме дают {}, а не 1", total).into());
+        }
+
+        Ok(BatchArrivals { size_probabilities, policy })
+    }
+
+    /// Вычисляет средний размер группы.
+    /// # Возвращаемое значение
+    /// Математическое ожидание размера группы, тип: `f64`.
+    pub fn mean_size(&self) -> f64 {
+        self.size_probabilities.iter()
+            .enumerate()
+            .map(|(k, g)| (k + 1) as f64 * g)
+            .sum()
+    }
+}
@@ -0,0 +1,231 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::plot_options::{Figure, PlotOptions};
+
+/// Наибольшее число итераций метода Швейцера.
+const MAX_SCHWEITZER_ITERATIONS: usize = 10_000;
+
+/// Станция замкнутой сети с одним каналом и дисциплиной FIFO.
+#[derive(Debug, Clone)]
+pub struct ClosedStation {
+    pub name: String,      // Название станции
+    pub visit_ratio: f64,  // Среднее число посещений станции за один цикл задания
+    pub service_time: f64, // Среднее время обслуживания за одно посещение
+}
+
+/// Характеристики замкнутой сети при заданном числе заданий.
+#[derive(Debug, Clone)]
+pub struct PopulationMetrics {
+    pub population: usize,               // Число заданий в сети
+    pub throughput: f64,                 // Число циклов, завершаемых в единицу времени
+    pub response_time: f64,              // Среднее время одного цикла
+    pub station_response_times: Vec<f64>, // Среднее время пребывания на станции за цикл (с учётом числа посещений)
+    pub station_queue_lengths: Vec<f64>, // Среднее число заданий на станции
+    pub utilisations: Vec<f64>,          // Загрузка станций
+}
+
+/// Замкнутая сеть: фиксированное число заданий циркулирует между станциями.
+/// Решается анализом средних значений (MVA).
+pub struct ClosedNetwork {
+    pub stations: Vec<ClosedStation>, // Станции сети
+}
+
+impl ClosedStation {
+    /// Суммарная потребность задания в обслуживании на станции за цикл D = v·S.
+    pub fn service_demand(&self) -> f64 {
+        self.visit_ratio * self.service_time
+    }
+}
+
+impl ClosedNetwork {
+    pub fn new(stations: Vec<ClosedStation>) -> ClosedNetwork {
+        ClosedNetwork {
+            stations
+        }
+    }
+
+    /// Вычисляет характеристики сети по длинам очередей, которые застаёт поступающее задание.
+    /// По теореме о прибытии R_k = D_k·(1 + Q_k(N - 1)), X = N / Σ R_k, Q_k = X·R_k.
+    fn mva_step(&self, population: usize, arrival_queue_lengths: &[f64]) -> PopulationMetrics {
+        let station_response_times: Vec<f64> = self.stations.iter()
+            .zip(arrival_queue_lengths.iter())
+            .map(|(station, queue)| station.service_demand() * (1.0 + queue))
+            .collect();
+        let response_time: f64 = station_response_times.iter().sum();
+        let throughput = population as f64 / response_time;
+
+        PopulationMetrics {
+            population,
+            throughput,
+            response_time,
+            station_queue_lengths: station_response_times.iter().map(|r| throughput * r).collect(),
+            utilisations: self.stations.iter().map(|station| throughput * station.service_demand()).collect(),
+            station_response_times,
+        }
+    }
+
+    /// Точный анализ средних значений: рекурсия по числу заданий от 1 до `max_population`.
+    /// # Параметры
+    /// * `max_population` - Наибольшее число заданий.
+    /// # Возвращаемое значение
+    /// Характеристики для каждого числа заданий, тип: `Vec<PopulationMetrics>`.
+    pub fn exact_mva(&self, max_population: usize) -> Vec<PopulationMetrics> {
+        let mut queue_lengths = vec![0.0; self.stations.len()];
+        (1..=max_population).map(|population| {
+            let metrics = self.mva_step(population, &queue_lengths);
+            queue_lengths = metrics.station_queue_lengths.clone();
+            metrics
+        }).collect()
+    }
+
+    /// Приближённый анализ средних значений Швейцера: Q_k(N - 1) ≈ (N - 1) / N · Q_k(N).
+    /// Не требует рекурсии по числу заданий, поэтому применим при большом N.
+    /// При N = 0 сеть пуста и итерации не нужны.
+    /// # Параметры
+    /// * `population` - Число заданий.
+    /// * `tolerance` - Допустимое изменение длин очередей между итерациями.
+    /// # Возвращаемое значение
+    /// Характеристики сети либо ошибка, если итерации не сошлись за `MAX_SCHWEITZER_ITERATIONS`,
+    /// тип: `Result<PopulationMetrics, Box<dyn std::error::Error>>`.
+    pub fn schweitzer_mva(&self, population: usize, tolerance: f64) -> Result<PopulationMetrics, Box<dyn std::error::Error>> {
+        if population == 0 {
+            return Ok(self.mva_step(0, &vec![0.0; self.stations.len()]));
+        }
+
+        let share = population as f64 / self.stations.len() as f64;
+        let mut queue_lengths = vec![share; self.stations.len()];
+        let scale = (population as f64 - 1.0) / population as f64;
+
+        for _ in 0..MAX_SCHWEITZER_ITERATIONS {
+            let arrival_queue_lengths: Vec<f64> = queue_lengths.iter().map(|q| scale * q).collect();
+            let metrics = self.mva_step(population, &arrival_queue_lengths);
+            let change = metrics.station_queue_lengths.iter()
+                .zip(queue_lengths.iter())
+                .map(|(new, old)| (new - old).abs())
+                .fold(0.0, f64::max);
+            if change < tolerance {
+                return Ok(metrics);
+            }
+            queue_lengths = metrics.station_queue_lengths;
+        }
+
+        Err(format!(
+            "метод Швейцера не сошёлся за {} итераций при N = {}",
+            MAX_SCHWEITZER_ITERATIONS, population
+        ).into())
+    }
+
+    /// Строит график пропускной способности в зависимости от числа заданий.
+    /// # Параметры
+    /// * `curves` - Пары (название метода, характеристики по числу заданий).
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn std::error::Error>>`.
+    pub fn plot_throughput(&self, curves: &[(&str, Vec<PopulationMetrics>)], options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>> {
+        options.render(&ThroughputFigure { network: self, curves })
+    }
+}
+
+/// График пропускной способности замкнутой сети для `ClosedNetwork::plot_throughput`.
+struct ThroughputFigure<'a> {
+    network: &'a ClosedNetwork,
+    curves: &'a [(&'a str, Vec<PopulationMetrics>)],
+}
+
+impl Figure for ThroughputFigure<'_> {
+    fn default_name(&self) -> &str {
+        "closed_network_throughput"
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let curves = self.curves;
+
+        let max_population = curves.iter()
+            .flat_map(|(_, metrics)| metrics.iter().map(|m| m.population))
+            .max()
+            .unwrap_or(1);
+        // Пропускная способность ограничена сверху 1 / max D_k
+        let max_demand = self.network.stations.iter().map(ClosedStation::service_demand).fold(0.0, f64::max);
+        let max_y = 1.1 / max_demand;
+
+        let mut chart = ChartBuilder::on(root)
+            .caption(options.title_or(self.default_name(), "Пропускная способность от числа заданий", "Throughput vs Population"), ("sans-serif", 50).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(0..max_population + 1, 0.0..max_y)?;
+
+        chart.configure_mesh()
+            .x_desc(options.x_label_or(self.default_name(), "Число заданий", "Population"))
+            .y_desc(options.y_label_or(self.default_name(), "Пропускная способность", "Throughput"))
+            .draw()?;
+
+        let colors = [&RED, &BLUE, &GREEN, &MAGENTA, &BLACK];
+        for (i, (method, metrics)) in curves.iter().enumerate() {
+            let color = colors[i % colors.len()];
+            chart
+                .draw_series(LineSeries::new(metrics.iter().map(|m| (m.population, m.throughput)), color))?
+                .label(*method)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+
+        chart.draw_series(LineSeries::new(
+            [(0, 1.0 / max_demand), (max_population + 1, 1.0 / max_demand)],
+            BLACK.mix(0.4),
+        ))?;
+
+        chart
+            .configure_series_labels()
+            .position(options.legend_position.to_series_label_position())
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Две станции с потребностями D_1 = 1 и D_2 = 2 (вторая посещается дважды за цикл).
+    fn two_stations() -> ClosedNetwork {
+        ClosedNetwork::new(vec![
+            ClosedStation { name: "A".to_string(), visit_ratio: 1.0, service_time: 1.0 },
+            ClosedStation { name: "B".to_string(), visit_ratio: 2.0, service_time: 1.0 },
+        ])
+    }
+
+    #[test]
+    fn exact_mva_matches_product_form_for_two_stations() {
+        // При N = 2 мультипликативная форма p(n_1, n_2) ∝ D_1^{n_1}·D_2^{n_2} даёт веса
+        // (2, 0): 1, (1, 1): 2, (0, 2): 4, G(2) = 7, G(1) = D_1 + D_2 = 3,
+        // откуда X = G(1)/G(2) = 3/7, Q_1 = 4/7, Q_2 = 10/7
+        let metrics = two_stations().exact_mva(2);
+
+        assert!((metrics[0].throughput - 1.0 / 3.0).abs() < 1e-12);
+        assert!((metrics[1].throughput - 3.0 / 7.0).abs() < 1e-12);
+        assert!((metrics[1].response_time - 14.0 / 3.0).abs() < 1e-12);
+        assert!((metrics[1].station_queue_lengths[0] - 4.0 / 7.0).abs() < 1e-12);
+        assert!((metrics[1].station_queue_lengths[1] - 10.0 / 7.0).abs() < 1e-12);
+        assert!((metrics[1].utilisations[1] - 6.0 / 7.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn schweitzer_is_exact_for_a_single_job_and_close_for_more() {
+        let network = two_stations();
+        let exact = network.exact_mva(5);
+
+        let single = network.schweitzer_mva(1, 1e-12).unwrap();
+        assert!((single.throughput - exact[0].throughput).abs() < 1e-9);
+
+        let approximate = network.schweitzer_mva(5, 1e-12).unwrap();
+        assert!((approximate.throughput - exact[4].throughput).abs() / exact[4].throughput < 0.05);
+    }
+}
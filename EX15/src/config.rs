@@ -1,37 +1,288 @@
-
 use lazy_static::lazy_static;
 use std::sync::Arc;
 
+use crate::batch_arrivals::BatchArrivals;
+use crate::rate_distribution::RateDistribution;
+
 #[derive(Debug)]
 pub struct Config {
     pub num_channels: i32,
     pub queue_size: i32,
-    pub lambda_rate: i32,
-    pub mu_rate: i32,
-    pub initial_state: Arc<Vec<(&'static str, i32)>>,
+    pub lambda_rate: f64,
+    pub mu_rate: f64,
+    pub abandonment_rate: f64,
+    pub balking: Option<fn(i32) -> f64>,
+    pub batch_arrivals: Option<BatchArrivals>,
+    pub initial_state: Arc<Vec<(String, i32)>>,
     pub time: i32,
     pub num_iterations: i32,
     pub step_size: f64
 }
 
-/// Инициализация начальных даанных из условий задачи
+impl Config {
+    /// Формирует начальное состояние, в котором система пуста (S_0 = 1).
+    /// # Параметры
+    /// * `num_channels` - Количество каналов.
+    /// * `queue_size` - Ограничение на размер очереди.
+    /// # Возвращаемое значение
+    /// Пары (имя состояния, начальная вероятность), тип: `Arc<Vec<(String, i32)>>`.
+    pub fn empty_initial_state(num_channels: i32, queue_size: i32) -> Arc<Vec<(String, i32)>> {
+        Arc::new(
+            (0..=num_channels + queue_size)
+                .map(|i| (format!("S_{}", i), if i == 0 { 1 } else { 0 }))
+                .collect()
+        )
+    }
+}
+
 lazy_static! {
+    /// Инициализация начальных даанных из условий задачи
     pub static ref QUEUING_SYSTEM_CONFIG: Config = Config {
         num_channels: 3,
         queue_size: 3,
-        lambda_rate: 30,
-        mu_rate: 5,
-        initial_state: Arc::new(vec![
-            ("S_0", 1),
-            ("S_1", 0),
-            ("S_2", 0),
-            ("S_3", 0),
-            ("S_4", 0),
-            ("S_5", 0),
-            ("S_6", 0),
-        ]),
+        lambda_rate: 30.0,
+        mu_rate: 5.0,
+        abandonment_rate: 0.0,
+        balking: None,
+        batch_arrivals: None,
+        initial_state: Config::empty_initial_state(3, 3),
         time: 1,
         num_iterations: 100,
         step_size: 0.01
     };
 }
+
+#[derive(Debug)]
+pub struct FiniteSourceConfig {
+    pub num_channels: i32,
+    pub num_sources: i32,
+    pub queue_size: i32,
+    pub lambda_rate: f64,
+    pub mu_rate: f64,
+    pub time: i32
+}
+
+lazy_static! {
+    /// Модель ремонта станков: бригада из двух ремонтников обслуживает десять станков
+    pub static ref FINITE_SOURCE_CONFIG: FiniteSourceConfig = FiniteSourceConfig {
+        num_channels: 2,
+        num_sources: 10,
+        queue_size: 8,
+        lambda_rate: 0.5,
+        mu_rate: 2.0,
+        time: 1
+    };
+}
+
+#[derive(Debug)]
+pub struct HeterogeneousConfig {
+    pub mu_rates: Vec<f64>,
+    pub queue_size: i32,
+    pub lambda_rate: f64,
+    pub time: i32
+}
+
+lazy_static! {
+    /// Офицеры с разной скоростью обработки при той же суммарной интенсивности 15
+    pub static ref HETEROGENEOUS_CONFIG: HeterogeneousConfig = HeterogeneousConfig {
+        mu_rates: vec![8.0, 5.0, 2.0],
+        queue_size: 3,
+        lambda_rate: 30.0,
+        time: 1
+    };
+}
+
+#[derive(Debug)]
+pub struct PriorityConfig {
+    pub num_channels: i32,
+    pub queue_size: i32,
+    pub lambda_rates: Vec<f64>,
+    pub mu_rates: Vec<f64>
+}
+
+lazy_static! {
+    /// Срочные и плановые обращения, обслуживаемые одними и теми же офицерами
+    pub static ref PRIORITY_CONFIG: PriorityConfig = PriorityConfig {
+        num_channels: 3,
+        queue_size: 3,
+        lambda_rates: vec![5.0, 10.0],
+        mu_rates: vec![6.0, 5.0]
+    };
+}
+
+lazy_static! {
+    /// Распределение размера группы одновременно поступающих обращений: 1, 2 или 3 заявки
+    pub static ref BATCH_SIZE_PROBABILITIES: Vec<f64> = vec![0.6, 0.3, 0.1];
+}
+
+#[derive(Debug)]
+pub struct ReliabilityConfig {
+    pub failure_rate: f64,
+    pub repair_rate: f64
+}
+
+lazy_static! {
+    /// Отказы и восстановление каналов: в среднем 0.5 отказа в час, ремонт около 30 минут
+    pub static ref RELIABILITY_CONFIG: ReliabilityConfig = ReliabilityConfig {
+        failure_rate: 0.5,
+        repair_rate: 2.0
+    };
+}
+
+#[derive(Debug)]
+pub struct SingleChannelConfig {
+    pub lambda_rate: f64,
+    pub mu_rate: f64,
+    pub service_cvs: Vec<f64>,
+    pub time: i32
+}
+
+lazy_static! {
+    /// Одноканальная система для сравнения M/M/1, M/D/1 и M/G/1
+    pub static ref SINGLE_CHANNEL_CONFIG: SingleChannelConfig = SingleChannelConfig {
+        lambda_rate: 4.0,
+        mu_rate: 5.0,
+        service_cvs: vec![0.5, 2.0],
+        time: 1
+    };
+}
+
+#[derive(Debug)]
+pub struct GGSConfig {
+    pub num_channels: i32,
+    pub arrival_scv: f64,
+    pub service_scv: f64,
+    pub time: i32
+}
+
+lazy_static! {
+    /// Приближения G/G/s: поток обращений неравномернее пуассоновского,
+    /// каналов достаточно, чтобы система с λ = 30 и μ = 5 была устойчивой
+    pub static ref GGS_CONFIG: GGSConfig = GGSConfig {
+        num_channels: 8,
+        arrival_scv: 2.0,
+        service_scv: 1.0,
+        time: 1
+    };
+}
+
+#[derive(Debug)]
+pub struct PhaseTypeConfig {
+    pub erlang_phases: usize,
+    pub hyperexponential_probabilities: Vec<f64>,
+    pub hyperexponential_rates: Vec<f64>,
+    pub coxian_rates: Vec<f64>,
+    pub coxian_continue_probabilities: Vec<f64>
+}
+
+lazy_static! {
+    /// Фазовые распределения времени обслуживания со средним 0.2 (μ = 5):
+    /// Эрланг с тремя фазами, гиперэкспоненциальное с равными вкладами фаз и Кокса с двумя фазами
+    pub static ref PHASE_TYPE_CONFIG: PhaseTypeConfig = PhaseTypeConfig {
+        erlang_phases: 3,
+        hyperexponential_probabilities: vec![0.8, 0.2],
+        hyperexponential_rates: vec![8.0, 2.0],
+        coxian_rates: vec![10.0, 2.5],
+        coxian_continue_probabilities: vec![0.25]
+    };
+}
+
+#[derive(Debug)]
+pub struct NetworkConfig {
+    pub station_names: Vec<&'static str>,
+    pub num_channels: Vec<i32>,
+    pub mu_rates: Vec<f64>,
+    pub queue_sizes: Vec<i32>,
+    pub external_arrival_rates: Vec<f64>,
+    pub routing: Vec<Vec<f64>>
+}
+
+lazy_static! {
+    /// Обработка обращений: первичный разбор, исполнение и утверждение.
+    /// После разбора 20% обращений закрываются сразу, 10% утверждённых возвращаются на доработку.
+    /// Размеры очередей используются только в модели с ограниченными очередями
+    pub static ref NETWORK_CONFIG: NetworkConfig = NetworkConfig {
+        station_names: vec!["Разбор", "Исполнение", "Утверждение"],
+        num_channels: vec![1, 3, 2],
+        mu_rates: vec![15.0, 4.0, 6.5],
+        queue_sizes: vec![5, 5, 3],
+        external_arrival_rates: vec![10.0, 0.0, 0.0],
+        routing: vec![
+            vec![0.0, 0.8, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 0.1, 0.0],
+        ]
+    };
+}
+
+#[derive(Debug)]
+pub struct ClosedNetworkConfig {
+    pub station_names: Vec<&'static str>,
+    pub visit_ratios: Vec<f64>,
+    pub service_times: Vec<f64>,
+    pub max_population: usize
+}
+
+lazy_static! {
+    /// Вычислительная ферма: задание обращается к процессору, диску и сети
+    pub static ref CLOSED_NETWORK_CONFIG: ClosedNetworkConfig = ClosedNetworkConfig {
+        station_names: vec!["CPU", "Disk", "Network"],
+        visit_ratios: vec![1.0, 8.0, 2.0],
+        service_times: vec![0.05, 0.008, 0.02],
+        max_population: 20
+    };
+}
+
+#[derive(Debug)]
+pub struct RetrialConfig {
+    pub retrial_rate: f64,
+    pub persistence: f64,
+    pub orbit_size: i32
+}
+
+lazy_static! {
+    /// Повторные звонки: абонент перезванивает в среднем через 10 минут (при часовой шкале)
+    /// и после каждой неудачной попытки с вероятностью 0.2 перестаёт звонить
+    pub static ref RETRIAL_CONFIG: RetrialConfig = RetrialConfig {
+        retrial_rate: 6.0,
+        persistence: 0.8,
+        orbit_size: 40
+    };
+}
+
+#[derive(Debug)]
+pub struct RewardConfig {
+    pub busy_channel_cost: f64,
+    pub waiting_cost: f64
+}
+
+lazy_static! {
+    /// Затраты смены: час работы офицера и штраф за час ожидания одной заявки
+    pub static ref REWARD_CONFIG: RewardConfig = RewardConfig {
+        busy_channel_cost: 500.0,
+        waiting_cost: 200.0
+    };
+}
+
+#[derive(Debug)]
+pub struct UncertaintyConfig {
+    pub scenarios: Vec<(&'static str, RateDistribution, RateDistribution)>,
+    pub num_samples: usize,
+    pub band: (f64, f64),
+    pub seed: u64
+}
+
+lazy_static! {
+    /// Неопределённость параметров: сценарии (название, распределение λ, распределение μ).
+    /// При наличии наблюдений к ним добавляется бутстреп по выборкам
+    pub static ref UNCERTAINTY_CONFIG: UncertaintyConfig = UncertaintyConfig {
+        scenarios: vec![
+            ("диапазон λ", RateDistribution::Uniform { lower: 25.0, upper: 35.0 }, RateDistribution::Fixed(5.0)),
+            ("нормальное", RateDistribution::Normal { mean: 30.0, std_dev: 2.5 }, RateDistribution::Normal { mean: 5.0, std_dev: 0.4 }),
+            ("логнормальное", RateDistribution::LogNormal { median: 30.0, sigma: 0.1 }, RateDistribution::LogNormal { median: 5.0, sigma: 0.1 }),
+        ],
+        num_samples: 500,
+        band: (0.05, 0.95),
+        seed: 42
+    };
+}
@@ -0,0 +1,270 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::statistics::{chi_squared_cdf, chi_squared_quantile, lilliefors_exponential_survival, PValue};
+
+/// Наблюдения за работой системы: моменты поступления заявок и длительности их обслуживания.
+#[derive(Debug, Clone)]
+pub struct Observations {
+    pub arrival_times: Vec<f64>,     // Моменты поступления заявок
+    pub service_durations: Vec<f64>, // Длительности обслуживания
+}
+
+/// Результат проверки согласия с экспоненциальным распределением.
+#[derive(Debug, Clone, Copy)]
+pub struct GoodnessOfFit {
+    pub statistic: f64,  // Значение статистики критерия
+    pub p_value: PValue, // Достигнутый уровень значимости
+}
+
+/// Оценка интенсивности с доверительным интервалом и проверками согласия.
+#[derive(Debug, Clone, Copy)]
+pub struct RateEstimate {
+    pub rate: f64,         // Оценка максимального правдоподобия
+    pub lower: f64,        // Нижняя граница доверительного интервала
+    pub upper: f64,        // Верхняя граница доверительного интервала
+    pub sample_size: usize, // Объём выборки
+    pub squared_cv: f64,   // Квадрат коэффициента вариации выборки (1 для экспоненциального распределения)
+    pub kolmogorov_smirnov: GoodnessOfFit,
+    pub chi_squared: GoodnessOfFit,
+}
+
+/// Оценки λ и μ, полученные по наблюдениям.
+#[derive(Debug, Clone)]
+pub struct ParameterEstimates {
+    pub lambda: RateEstimate,
+    pub mu: RateEstimate,
+    pub confidence_level: f64,
+}
+
+impl Observations {
+    /// Читает наблюдения из CSV-файла со столбцами `arrival_time,service_duration`.
+    /// Первая строка считается заголовком, пустое значение длительности допускается.
+    /// # Параметры
+    /// * `path` - Путь к CSV-файлу.
+    /// # Возвращаемое значение
+    /// Наблюдения, тип: `Result<Observations, Box<dyn Error>>`.
+    pub fn from_csv<P: AsRef<Path>>(path: P) -> Result<Observations, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+        let mut arrival_times = Vec::new();
+        let mut service_durations = Vec::new();
+
+        for (line_number, line) in content.lines().enumerate().skip(1) {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut columns = line.split(',').map(str::trim);
+            let arrival = columns.next().unwrap_or_default();
+            let service = columns.next().unwrap_or_default();
+
+            if !arrival.is_empty() {
+                arrival_times.push(arrival.parse::<f64>()
+                    .map_err(|e| format!("строка {}: некорректный момент поступления: {}", line_number + 1, e))?);
+            }
+            if !service.is_empty() {
+                service_durations.push(service.parse::<f64>()
+                    .map_err(|e| format!("строка {}: некорректная длительность обслуживания: {}", line_number + 1, e))?);
+            }
+        }
+
+        Ok(Observations { arrival_times, service_durations })
+    }
+
+    /// Вычисляет интервалы между последовательными поступлениями заявок.
+    /// # Возвращаемое значение
+    /// Интервалы между поступлениями, тип: `Vec<f64>`.
+    pub fn interarrival_times(&self) -> Vec<f64> {
+        self.arrival_times.windows(2).map(|w| w[1] - w[0]).collect()
+    }
+}
+
+impl RateEstimate {
+    /// Оценивает интенсивность экспоненциального распределения по выборке интервалов.
+    /// Точечная оценка — оценка максимального правдоподобия n / Σx,
+    /// доверительный интервал строится по точному распределению 2λΣx ~ χ²(2n).
+    /// # Параметры
+    /// * `sample` - Выборка длительностей (интервалов между событиями).
+    /// * `confidence_level` - Доверительная вероятность, например 0.95.
+    /// # Возвращаемое значение
+    /// Оценка интенсивности, тип: `Result<RateEstimate, Box<dyn Error>>`.
+    pub fn from_sample(sample: &[f64], confidence_level: f64) -> Result<RateEstimate, Box<dyn Error>> {
+        if sample.len() < 2 {
+            return Err("для оценки интенсивности нужно хотя бы два наблюдения".into());
+        }
+        if sample.iter().any(|&x| x < 0.0 || !x.is_finite()) {
+            return Err("длительности должны быть неотрицательными конечными числами".into());
+        }
+
+        let n = sample.len() as f64;
+        let total: f64 = sample.iter().sum();
+        if total <= 0.0 {
+            return Err("сумма длительностей должна быть положительной".into());
+        }
+
+        let rate = n / total;
+        let mean = total / n;
+        let variance = sample.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        let alpha = 1.0 - confidence_level;
+        let lower = chi_squared_quantile(alpha / 2.0, 2.0 * n) / (2.0 * total);
+        let upper = chi_squared_quantile(1.0 - alpha / 2.0, 2.0 * n) / (2.0 * total);
+
+        Ok(RateEstimate {
+            rate,
+            lower,
+            upper,
+            sample_size: sample.len(),
+            squared_cv: variance / mean.powi(2),
+            kolmogorov_smirnov: Self::kolmogorov_smirnov_test(sample, rate),
+            chi_squared: Self::chi_squared_test(sample, rate),
+        })
+    }
+
+    /// Критерий Колмогорова–Смирнова для экспоненциального распределения с интенсивностью `rate`.
+    /// Параметр оценён по той же выборке, поэтому используется поправка Лиллиефорса (Стивенса)
+    /// для экспоненциального распределения вместо распределения Колмогорова.
+    fn kolmogorov_smirnov_test(sample: &[f64], rate: f64) -> GoodnessOfFit {
+        let mut sorted = sample.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len() as f64;
+
+        let statistic = sorted.iter().enumerate().fold(0.0_f64, |acc, (i, &x)| {
+            let cdf = 1.0 - (-rate * x).exp();
+            let upper = (i as f64 + 1.0) / n - cdf;
+            let lower = cdf - i as f64 / n;
+            acc.max(upper).max(lower)
+        });
+
+        let modified = (statistic - 0.2 / n) * (n.sqrt() + 0.26 + 0.5 / n.sqrt());
+
+        GoodnessOfFit { statistic, p_value: lilliefors_exponential_survival(modified) }
+    }
+
+    /// Критерий χ² Пирсона для экспоненциального распределения с интенсивностью `rate`.
+    /// Интервалы группировки выбираются равновероятными, один параметр оценён по выборке.
+    /// Интервалы с ожидаемой частотой меньше 5 объединяются с соседними; если после объединения
+    /// остаётся меньше трёх интервалов, критерий неприменим и статистика и p-значение равны NaN.
+    fn chi_squared_test(sample: &[f64], rate: f64) -> GoodnessOfFit {
+        let n = sample.len();
+        let bins = (n / 5).clamp(3, 20);
+
+        let mut observed = vec![0usize; bins];
+        for &x in sample {
+            let cdf = 1.0 - (-rate * x).exp();
+            let bin = ((cdf * bins as f64) as usize).min(bins - 1);
+            observed[bin] += 1;
+        }
+        let groups = merge_sparse_bins(&observed, n as f64 / bins as f64);
+        if groups.len() < 3 {
+            return GoodnessOfFit { statistic: f64::NAN, p_value: PValue::Exact(f64::NAN) };
+        }
+
+        let statistic: f64 = groups.iter()
+            .map(|&(o, expected)| (o as f64 - expected).powi(2) / expected)
+            .sum();
+        let degrees_of_freedom = (groups.len() - 2) as f64;
+
+        GoodnessOfFit { statistic, p_value: PValue::Exact(1.0 - chi_squared_cdf(statistic, degrees_of_freedom)) }
+    }
+}
+
+/// Объединяет соседние интервалы группировки, пока ожидаемая частота каждого не станет не меньше 5.
+/// Остаток с недостаточной частотой присоединяется к последнему интервалу.
+/// # Параметры
+/// * `observed` - Наблюдаемые частоты интервалов.
+/// * `expected` - Ожидаемая частота одного интервала.
+/// # Возвращаемое значение
+/// Пары (наблюдаемая, ожидаемая частота) объединённых интервалов, тип: `Vec<(usize, f64)>`.
+fn merge_sparse_bins(observed: &[usize], expected: f64) -> Vec<(usize, f64)> {
+    const MIN_EXPECTED: f64 = 5.0;
+    let mut groups: Vec<(usize, f64)> = Vec::new();
+    let mut current = (0, 0.0);
+    for &o in observed {
+        current = (current.0 + o, current.1 + expected);
+        if current.1 >= MIN_EXPECTED {
+            groups.push(current);
+            current = (0, 0.0);
+        }
+    }
+    if current.1 > 0.0 {
+        match groups.last_mut() {
+            Some(last) => *last = (last.0 + current.0, last.1 + current.1),
+            None => groups.push(current),
+        }
+    }
+    groups
+}
+
+impl ParameterEstimates {
+    /// Оценивает λ по интервалам между поступлениями и μ по длительностям обслуживания.
+    /// # Параметры
+    /// * `observations` - Наблюдения за системой.
+    /// * `confidence_level` - Доверительная вероятность, например 0.95.
+    /// # Возвращаемое значение
+    /// Оценки параметров, тип: `Result<ParameterEstimates, Box<dyn Error>>`.
+    pub fn from_observations(observations: &Observations, confidence_level: f64) -> Result<ParameterEstimates, Box<dyn Error>> {
+        if observations.arrival_times.windows(2).any(|w| w[1] < w[0]) {
+            return Err("моменты поступления заявок должны быть упорядочены по возрастанию".into());
+        }
+
+        Ok(ParameterEstimates {
+            lambda: RateEstimate::from_sample(&observations.interarrival_times(), confidence_level)?,
+            mu: RateEstimate::from_sample(&observations.service_durations, confidence_level)?,
+            confidence_level,
+        })
+    }
+
+    /// Формирует конфигурацию СМО с оценёнными интенсивностями.
+    /// # Параметры
+    /// * `base` - Конфигурация, из которой берутся структура системы и параметры интегрирования.
+    /// # Возвращаемое значение
+    /// Готовая конфигурация, тип: `Config`.
+    pub fn to_config(&self, base: &Config) -> Config {
+        Config {
+            num_channels: base.num_channels,
+            queue_size: base.queue_size,
+            lambda_rate: self.lambda.rate,
+            mu_rate: self.mu.rate,
+            abandonment_rate: base.abandonment_rate,
+            balking: base.balking,
+            batch_arrivals: base.batch_arrivals.clone(),
+            initial_state: Config::empty_initial_state(base.num_channels, base.queue_size),
+            time: base.time,
+            num_iterations: base.num_iterations,
+            step_size: base.step_size
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_estimate_on_fixed_sample() {
+        let estimate = RateEstimate::from_sample(&[0.5, 1.0, 1.5, 2.0], 0.95).unwrap();
+        // λ = n / Σx = 4 / 5, интервал по квантилям χ²(8): 2.1797 / 10 и 17.5345 / 10
+        assert!((estimate.rate - 0.8).abs() < 1e-12);
+        assert!((estimate.lower - 0.21797).abs() < 1e-4);
+        assert!((estimate.upper - 1.75345).abs() < 1e-4);
+        assert_eq!(estimate.sample_size, 4);
+        // s² = 0.41667, x̄ = 1.25
+        assert!((estimate.squared_cv - 0.41667 / 1.5625).abs() < 1e-4);
+    }
+
+    #[test]
+    fn rate_estimate_rejects_invalid_samples() {
+        assert!(RateEstimate::from_sample(&[1.0], 0.95).is_err());
+        assert!(RateEstimate::from_sample(&[1.0, -1.0], 0.95).is_err());
+        assert!(RateEstimate::from_sample(&[0.0, 0.0], 0.95).is_err());
+    }
+
+    #[test]
+    fn interarrival_times_are_differences_of_arrivals() {
+        let observations = Observations { arrival_times: vec![0.0, 1.5, 2.0], service_durations: vec![] };
+        assert_eq!(observations.interarrival_times(), vec![1.5, 0.5]);
+    }
+}
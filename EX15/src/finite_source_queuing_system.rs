@@ -0,0 +1,278 @@
+use std::cmp::Ordering::{Equal, Greater, Less};
+use std::collections::BTreeMap;
+
+use nalgebra::DVector;
+
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+/// Замкнутая СМО с конечным числом источников (модель ремонта станков, Энгсет).
+/// Каждый из N станков отказывает с интенсивностью λ только пока работает,
+/// поэтому в состоянии S_k (k неисправных станков) интенсивность потока равна (N - k)·λ.
+pub struct FiniteSourceQueuingSystem {
+    pub lambda_rate: f64,  // Интенсивность отказов одного работающего станка
+    pub mu_rate: f64,      // Интенсивность ремонта одним рабочим
+    pub num_channels: i32, // Количество ремонтников
+    pub num_sources: i32,  // Количество станков N
+    pub queue_size: i32,   // Ограничение на число станков, ожидающих ремонта
+    pub time: i32,         // Время
+}
+
+impl FiniteSourceQueuingSystem {
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               num_channels: i32,
+               num_sources: i32,
+               queue_size: i32,
+               time: i32
+    ) -> FiniteSourceQueuingSystem {
+
+        FiniteSourceQueuingSystem {
+            lambda_rate,
+            mu_rate,
+            num_channels,
+            num_sources,
+            queue_size,
+            time
+        }
+    }
+
+    /// Номер последнего состояния: min(N, s + n).
+    fn max_state(&self) -> usize {
+        self.num_sources.min(self.num_channels + self.queue_size) as usize
+    }
+
+    /// Интенсивность перехода из состояния S_k в S_{k+1}: (N - k)·λ.
+    /// # Параметры
+    /// * `k` - Число неисправных станков.
+    /// # Возвращаемое значение
+    /// Интенсивность перехода, тип: `f64`.
+    pub fn birth_rate(&self, k: usize) -> f64 {
+        if k < self.max_state() { (self.num_sources as usize - k) as f64 * self.lambda_rate } else { 0.0 }
+    }
+
+    /// Интенсивность перехода из состояния S_k в S_{k-1}: min(k, s)·μ.
+    /// # Параметры
+    /// * `k` - Число неисправных станков.
+    /// # Возвращаемое значение
+    /// Интенсивность перехода, тип: `f64`.
+    pub fn death_rate(&self, k: usize) -> f64 {
+        k.min(self.num_channels as usize) as f64 * self.mu_rate
+    }
+
+    pub fn generate_kolmogorov_matrix(&self) -> Vec<Vec<f64>> {
+        let number_of_states = self.max_state() + 1;
+
+        (0..number_of_states).map(|i| {
+            (0..number_of_states).map(|j| {
+                match i.cmp(&j) {
+                    Equal => - (self.birth_rate(i) + self.death_rate(i)),
+                    Less => if j == i + 1 { self.death_rate(j) } else { 0.0 },
+                    Greater => if j == i - 1 { self.birth_rate(j) } else { 0.0 },
+                }
+            }).collect()
+        }).collect()
+    }
+
+    /// Вычисляет стационарное распределение в замкнутой форме:
+    /// p_k = p_0·C(N, k)·ρ^k при k ≤ s и p_k = p_0·C(N, k)·ρ^k·k! / (s!·s^(k-s)) при k > s.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний S_0..S_K, тип: `DVector<f64>`.
+    pub fn stationary_distribution(&self) -> DVector<f64> {
+        let ksi = self.calculate_load_factor();
+        let s = self.num_channels as usize;
+        let n = self.num_sources as u64;
+
+        let weights: Vec<f64> = (0..=self.max_state()).map(|k| {
+            let binomial = (0..k as u64).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64);
+            let queue_factor = if k <= s {
+                1.0
+            } else {
+                (s + 1..=k).map(|i| i as f64 / s as f64).product()
+            };
+            binomial * ksi.powi(k as i32) * queue_factor
+        }).collect();
+
+        let total: f64 = weights.iter().sum();
+        DVector::from_iterator(weights.len(), weights.into_iter().map(|w| w / total))
+    }
+
+    /// Интенсивность потока отказов станков, поступающих в систему: λ·Σ (N - k)·p_k.
+    fn calculate_effective_arrival_rate(&self) -> f64 {
+        let distribution = self.stationary_distribution();
+        let max_state = self.max_state();
+
+        distribution.iter()
+            .enumerate()
+            .filter(|(k, _)| *k < max_state)
+            .map(|(k, p)| (self.num_sources as usize - k) as f64 * self.lambda_rate * p)
+            .sum()
+    }
+
+    /// Вычисляет коэффициент использования ремонтной бригады.
+    /// # Возвращаемое значение
+    /// Доля времени, в течение которой ремонтник занят, тип: `f64`.
+    pub fn calculate_crew_utilisation(&self) -> f64 {
+        self.calculate_average_busy_channels() / self.num_channels as f64
+    }
+
+    /// Вычисляет коэффициент готовности станков.
+    /// # Возвращаемое значение
+    /// Средняя доля работающих станков (N - L) / N, тип: `f64`.
+    pub fn calculate_machine_availability(&self) -> f64 {
+        1.0 - self.calculate_total_number_of_requests() / self.num_sources as f64
+    }
+}
+
+impl QueuingSystemCharacteristics for FiniteSourceQueuingSystem {
+    /// 1
+    /// Вычисляет коэффициент загрузки одного станка ρ = λ / μ.
+    /// # Возвращаемое значение
+    /// Коэффициент загрузки, тип: `f64`.
+    fn calculate_load_factor(&self) -> f64 {
+        self.lambda_rate / self.mu_rate
+    }
+
+    /// 2
+    /// Вычисляет вероятность того, что все станки исправны (P0).
+    /// # Возвращаемое значение
+    /// Вероятность простоя ремонтной бригады, тип: `f64`.
+    fn calculate_probability_of_downtime(&self) -> f64 {
+        self.stationary_distribution()[0]
+    }
+
+    /// Вычисляет факториал числа.
+    /// # Параметры
+    /// * `n` - Число, для которого вычисляется факториал.
+    /// # Возвращаемое значение
+    /// Факториал заданного числа, тип: `u64`.
+    fn factorial(n: u64) -> u64 {
+        (1..=n).product()
+    }
+
+    /// 3
+    /// Вероятность того, что i ремонтников заняты и нет очереди.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_probabilities(&self) -> BTreeMap<String, f64> {
+        let distribution = self.stationary_distribution();
+        let last = (self.num_channels as usize).min(self.max_state());
+
+        (0..=last)
+            .map(|i| (format!("P_{}", i), distribution[i]))
+            .collect()
+    }
+
+    /// 4
+    /// Вероятность того, что все s ремонтников заняты и i станков ждут ремонта.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы с очередью) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_queue_probabilities(&self) -> BTreeMap<String, f64> {
+        let distribution = self.stationary_distribution();
+        let s = self.num_channels as usize;
+
+        (s + 1..=self.max_state())
+            .map(|k| (format!("P{}", k - s), distribution[k]))
+            .collect()
+    }
+
+    /// 5
+    /// Вероятность отказа по Энгсету: доля отказавших станков, заставших систему заполненной.
+    /// # Возвращаемое значение
+    /// (N - K)·p_K / Σ (N - k)·p_k, равна нулю, если в системе помещаются все N станков, тип: `f64`.
+    fn calculate_rejection_probability(&self) -> f64 {
+        let distribution = self.stationary_distribution();
+        let max_state = self.max_state();
+        let n = self.num_sources as usize;
+
+        let blocked = (n - max_state) as f64 * distribution[max_state];
+        let offered: f64 = distribution.iter()
+            .enumerate()
+            .map(|(k, p)| (n - k) as f64 * p)
+            .sum();
+
+        blocked / offered
+    }
+
+    /// 6
+    /// Вычисляет среднее количество отказов станков, поступающих в систему за время T.
+    /// # Возвращаемое значение
+    /// Среднее количество заявок на ремонт за указанный период времени, тип: `f64`.
+    fn calculate_average_incoming_requests_during_t(&self) -> f64 {
+        self.calculate_effective_arrival_rate() * self.time as f64
+    }
+
+    /// 7
+    /// Вычисляет среднее время ремонта одного станка.
+    /// # Возвращаемое значение
+    /// Среднее время ремонта, тип: `f64`.
+    fn calculate_average_service_time_per_request(&self) -> f64 {
+        1.0 / self.mu_rate
+    }
+
+    /// 8
+    /// Вычисляет среднее время обслуживания одним каналом заявок, поступивших за время T.
+    /// # Возвращаемое значение
+    /// Среднее время обслуживания заявок одним каналом за время T, тип: `f64`.
+    fn average_service_time_per_channel_for_t(&self) -> f64 {
+        self.calculate_load_factor() * self.time as f64
+    }
+
+    /// 9
+    /// Вычисляет среднее число занятых ремонтников.
+    /// # Возвращаемое значение
+    /// Σ min(k, s)·p_k, тип: `f64`.
+    fn calculate_average_busy_channels(&self) -> f64 {
+        let s = self.num_channels as usize;
+        self.stationary_distribution().iter()
+            .enumerate()
+            .map(|(k, p)| k.min(s) as f64 * p)
+            .sum()
+    }
+
+    /// 10
+    /// Вычисляет среднее число станков, ожидающих ремонта.
+    /// # Возвращаемое значение
+    /// Σ (k - s)·p_k по k > s, тип: `f64`.
+    fn calculate_average_number_of_requests_in_queue(&self) -> f64 {
+        let s = self.num_channels as usize;
+        self.stationary_distribution().iter()
+            .enumerate()
+            .map(|(k, p)| k.saturating_sub(s) as f64 * p)
+            .sum()
+    }
+
+    /// 12
+    /// Вычисляет среднее время ожидания ремонта.
+    /// # Возвращаемое значение
+    /// Lq / λ_эфф по формуле Литтла, тип: `f64`.
+    fn calculate_average_waiting_time_in_queue(&self) -> f64 {
+        self.calculate_average_number_of_requests_in_queue() / self.calculate_effective_arrival_rate()
+    }
+
+    /// 13
+    /// Вычисляет среднее число неисправных станков.
+    /// # Возвращаемое значение
+    /// Σ k·p_k, тип: `f64`.
+    fn calculate_total_number_of_requests(&self) -> f64 {
+        self.stationary_distribution().iter()
+            .enumerate()
+            .map(|(k, p)| k as f64 * p)
+            .sum()
+    }
+
+    /// 14
+    /// Вычисляет среднее время ожидания ремонта в системе.
+    /// # Возвращаемое значение
+    /// Среднее время ожидания, тип: `f64`.
+    fn calculate_average_waiting_time(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue()
+    }
+
+    /// 15
+    /// Вычисляет среднее время простоя станка (ожидание и ремонт).
+    /// # Возвращаемое значение
+    /// L / λ_эфф по формуле Литтла, тип: `f64`.
+    fn calculate_average_time_in_system(&self) -> f64 {
+        self.calculate_total_number_of_requests() / self.calculate_effective_arrival_rate()
+    }
+}
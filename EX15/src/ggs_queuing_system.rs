@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+/// Приближение для вероятности ожидания в СМО G/G/s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GGSApproximation {
+    Kingman,      // Формула Кингмана, обобщённая на s каналов: P(ожидания) ≈ ρ^(√(2(s+1)) - 1)
+    AllenCunneen, // Формула Аллена–Каннина: P(ожидания) по формуле Эрланга C
+}
+
+/// Многоканальная СМО G/G/s с неограниченной очередью.
+/// Среднее время ожидания приближается поправкой к M/M/s:
+/// Wq ≈ P(ожидания) · (c_a² + c_s²) / 2 · 1 / (sμ - λ),
+/// где c_a² и c_s² — квадраты коэффициентов вариации интервалов между заявками и времени обслуживания.
+/// При c_a² = c_s² = 1 приближение Аллена–Каннина совпадает с точным результатом M/M/s.
+pub struct GGSQueuingSystem {
+    pub lambda_rate: f64,   // Интенсивность потока заявок
+    pub mu_rate: f64,       // Интенсивность обработки одним каналом
+    pub num_channels: i32,  // Количество каналов
+    pub arrival_scv: f64,   // Квадрат коэффициента вариации интервалов между заявками
+    pub service_scv: f64,   // Квадрат коэффициента вариации времени обслуживания
+    pub approximation: GGSApproximation, // Приближение для вероятности ожидания
+    pub time: i32,          // Время
+}
+
+impl GGSQueuingSystem {
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               num_channels: i32,
+               arrival_scv: f64,
+               service_scv: f64,
+               approximation: GGSApproximation,
+               time: i32
+    ) -> GGSQueuingSystem {
+
+        GGSQueuingSystem {
+            lambda_rate,
+            mu_rate,
+            num_channels,
+            arrival_scv,
+            service_scv,
+            approximation,
+            time
+        }
+    }
+
+    /// Создаёт систему M/M/s с теми же λ, μ и s: пуассоновский поток и экспоненциальное обслуживание.
+    pub fn markovian(lambda_rate: f64, mu_rate: f64, num_channels: i32, time: i32) -> GGSQueuingSystem {
+        GGSQueuingSystem::new(lambda_rate, mu_rate, num_channels, 1.0, 1.0, GGSApproximation::AllenCunneen, time)
+    }
+
+    /// Вычисляет загрузку одного канала ρ = λ / (sμ).
+    /// # Возвращаемое значение
+    /// Загрузка канала, тип: `f64`.
+    pub fn calculate_utilisation(&self) -> f64 {
+        self.lambda_rate / (self.num_channels as f64 * self.mu_rate)
+    }
+
+    /// Вычисляет вероятность ожидания по формуле Эрланга C для M/M/s:
+    /// C = ξ^s / (s!·(1 - ρ)) · P0.
+    /// # Возвращаемое значение
+    /// Вероятность того, что заявка застанет все каналы занятыми, тип: `f64`.
+    pub fn calculate_erlang_c(&self) -> f64 {
+        let ro = self.calculate_utilisation();
+        if ro >= 1.0 {
+            return 1.0;
+        }
+
+        let ksi = self.calculate_load_factor();
+        let s = self.num_channels;
+        self.calculate_probability_of_downtime() * ksi.powi(s) / (Self::factorial(s as u64) as f64 * (1.0 - ro))
+    }
+
+    /// Вычисляет вероятность ожидания выбранным приближением.
+    /// # Возвращаемое значение
+    /// Вероятность того, что заявке придётся ждать, тип: `f64`.
+    pub fn calculate_waiting_probability(&self) -> f64 {
+        match self.approximation {
+            GGSApproximation::Kingman => {
+                let exponent = (2.0 * (self.num_channels as f64 + 1.0)).sqrt() - 1.0;
+                self.calculate_utilisation().min(1.0).powf(exponent)
+            }
+            GGSApproximation::AllenCunneen => self.calculate_erlang_c(),
+        }
+    }
+}
+
+impl QueuingSystemCharacteristics for GGSQueuingSystem {
+    /// 1
+    /// Вычисляет коэффициент загрузки СМО ξ = λ / μ.
+    /// # Возвращаемое значение
+    /// Коэффициент загрузки системы СМО, тип: `f64`.
+    fn calculate_load_factor(&self) -> f64 {
+        self.lambda_rate / self.mu_rate
+    }
+
+    /// 2
+    /// Вычисляет вероятность простоя системы P0 для M/M/s с неограниченной очередью.
+    /// Для G/G/s используется как приближение.
+    /// # Возвращаемое значение
+    /// Вероятность простоя системы, 0 при ρ ≥ 1, тип: `f64`.
+    fn calculate_probability_of_downtime(&self) -> f64 {
+        let ro = self.calculate_utilisation();
+        if ro >= 1.0 {
+            return 0.0;
+        }
+
+        let ksi = self.calculate_load_factor();
+        let s = self.num_channels;
+        let sum: f64 = (0..s)
+            .map(|i| ksi.powi(i) / Self::factorial(i as u64) as f64)
+            .sum();
+        1.0 / (sum + ksi.powi(s) / (Self::factorial(s as u64) as f64 * (1.0 - ro)))
+    }
+
+    /// Вычисляет факториал числа.
+    /// # Параметры
+    /// * `n` - Число, для которого вычисляется факториал.
+    /// # Возвращаемое значение
+    /// Факториал заданного числа, тип: `u64`.
+    fn factorial(n: u64) -> u64 {
+        (1..=n).product()
+    }
+
+    /// 3
+    /// Вероятность простоя. Вероятности остальных состояний G/G/s
+    /// не определяются коэффициентами вариации.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_probabilities(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("P_0".to_string(), self.calculate_probability_of_downtime())])
+    }
+
+    /// 4
+    /// Распределение длины очереди G/G/s не определяется коэффициентами вариации.
+    /// # Возвращаемое значение
+    /// Пустая таблица, тип: `BTreeMap<String, f64>`.
+    fn calculate_queue_probabilities(&self) -> BTreeMap<String, f64> {
+        BTreeMap::new()
+    }
+
+    /// 5
+    /// В системе с неограниченной очередью отказов нет.
+    /// # Возвращаемое значение
+    /// Вероятность отказа, тип: `f64`.
+    fn calculate_rejection_probability(&self) -> f64 {
+        0.0
+    }
+
+    /// 6
+    /// Вычисляет среднее количество заявок, поступающих в систему за время T.
+    /// # Возвращаемое значение
+    /// Среднее количество заявок за указанный период времени, тип: `f64`.
+    fn calculate_average_incoming_requests_during_t(&self) -> f64 {
+        self.lambda_rate * self.time as f64
+    }
+
+    /// 7
+    /// Вычисляет среднее время обслуживания одной заявки.
+    /// # Возвращаемое значение
+    /// Среднее время, необходимое для обслуживания одной заявки, тип: `f64`.
+    fn calculate_average_service_time_per_request(&self) -> f64 {
+        1.0 / self.mu_rate
+    }
+
+    /// 8
+    /// Вычисляет среднее время обслуживания одним каналом заявок, поступивших за время T.
+    /// # Возвращаемое значение
+    /// Среднее время обслуживания заявок одним каналом за время T, тип: `f64`.
+    fn average_service_time_per_channel_for_t(&self) -> f64 {
+        self.calculate_load_factor() * self.time as f64
+    }
+
+    /// 9
+    /// Вычисляет среднее число занятых каналов (равно ξ при ρ < 1).
+    /// # Возвращаемое значение
+    /// Среднее количество занятых каналов, тип: `f64`.
+    fn calculate_average_busy_channels(&self) -> f64 {
+        self.calculate_load_factor().min(self.num_channels as f64)
+    }
+
+    /// 10
+    /// Вычисляет среднюю длину очереди Lq = λ·Wq.
+    /// # Возвращаемое значение
+    /// Среднее количество заявок в очереди, бесконечность при ρ ≥ 1, тип: `f64`.
+    fn calculate_average_number_of_requests_in_queue(&self) -> f64 {
+        self.lambda_rate * self.calculate_average_waiting_time_in_queue()
+    }
+
+    /// 12
+    /// Вычисляет среднее время пребывания заявки в очереди:
+    /// Wq ≈ P(ожидания) · (c_a² + c_s²) / 2 · 1 / (sμ - λ).
+    /// # Возвращаемое значение
+    /// Среднее время ожидания в очереди, бесконечность при ρ ≥ 1, тип: `f64`.
+    fn calculate_average_waiting_time_in_queue(&self) -> f64 {
+        if self.calculate_utilisation() >= 1.0 {
+            return f64::INFINITY;
+        }
+
+        let variability = (self.arrival_scv + self.service_scv) / 2.0;
+        let capacity_margin = self.num_channels as f64 * self.mu_rate - self.lambda_rate;
+        self.calculate_waiting_probability() * variability / capacity_margin
+    }
+
+    /// 13
+    /// Вычисляет общее количество заявок в системе.
+    /// # Возвращаемое значение
+    /// L = Lq + ξ, тип: `f64`.
+    fn calculate_total_number_of_requests(&self) -> f64 {
+        self.calculate_average_number_of_requests_in_queue() + self.calculate_load_factor()
+    }
+
+    /// 14
+    /// Вычисляет среднее время ожидания заявки в системе.
+    /// # Возвращаемое значение
+    /// Среднее время ожидания заявки в системе, тип: `f64`.
+    fn calculate_average_waiting_time(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue()
+    }
+
+    /// 15
+    /// Вычисляет среднее время пребывания заявки в системе.
+    /// # Возвращаемое значение
+    /// W = Wq + 1/μ, тип: `f64`.
+    fn calculate_average_time_in_system(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue() + self.calculate_average_service_time_per_request()
+    }
+}
@@ -0,0 +1,250 @@
+use std::error::Error;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::markov_chain;
+use crate::stationary_characteristics::StationaryCharacteristics;
+
+/// Правило выбора свободного канала для поступившей заявки.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssignmentPolicy {
+    FastestFreeFirst, // Самый быстрый из свободных каналов
+    Random,           // Равновероятно любой свободный канал
+    RoundRobin,       // Первый свободный канал по кругу после последнего назначенного
+}
+
+/// Наибольшее число каналов. Число состояний растёт как 2^s (при круговом назначении — как s·2^s),
+/// а матрица уравнений Колмогорова плотная: при s = 8 в ней около двух тысяч строк.
+const MAX_CHANNELS: usize = 8;
+
+/// Состояние СМО с разнородными каналами: какие каналы заняты, длина очереди
+/// и (для кругового назначения) номер канала, с которого начинается поиск свободного.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelState {
+    pub busy: u32,      // Битовая маска занятых каналов
+    pub queue: i32,     // Количество заявок в очереди
+    pub pointer: usize, // Следующий канал для кругового назначения
+}
+
+/// СМО, в которой каждый канал обслуживает заявки со своей интенсивностью μ_i.
+pub struct HeterogeneousQueuingSystem {
+    pub lambda_rate: f64,          // Интенсивность потока заявок
+    pub mu_rates: Vec<f64>,        // Интенсивности обслуживания каждого канала
+    pub queue_size: i32,           // Ограничение на размер очереди
+    pub policy: AssignmentPolicy,  // Правило выбора свободного канала
+    pub time: i32,                 // Время
+}
+
+impl ChannelState {
+    /// Число заявок в системе: занятые каналы плюс очередь.
+    pub fn customers(&self) -> usize {
+        self.busy.count_ones() as usize + self.queue as usize
+    }
+}
+
+impl HeterogeneousQueuingSystem {
+    /// Создаёт СМО с разнородными каналами.
+    /// # Возвращаемое значение
+    /// Модель либо ошибка, если каналов нет, их больше `MAX_CHANNELS` или какая-либо μ_i не положительна,
+    /// тип: `Result<HeterogeneousQueuingSystem, Box<dyn Error>>`.
+    pub fn new(lambda_rate: f64,
+               mu_rates: Vec<f64>,
+               queue_size: i32,
+               policy: AssignmentPolicy,
+               time: i32
+    ) -> Result<HeterogeneousQueuingSystem, Box<dyn Error>> {
+        if mu_rates.is_empty() || mu_rates.len() > MAX_CHANNELS {
+            return Err(format!("число каналов должно быть от 1 до {}, задано {}", MAX_CHANNELS, mu_rates.len()).into());
+        }
+        if let Some(mu_rate) = mu_rates.iter().find(|&&mu_rate| !(mu_rate > 0.0 && mu_rate.is_finite())) {
+            return Err(format!("интенсивности обслуживания каналов должны быть положительными, задано {}", mu_rate).into());
+        }
+
+        Ok(HeterogeneousQueuingSystem {
+            lambda_rate,
+            mu_rates,
+            queue_size,
+            policy,
+            time
+        })
+    }
+
+    fn num_channels(&self) -> usize {
+        self.mu_rates.len()
+    }
+
+    fn all_busy(&self) -> u32 {
+        (1u32 << self.num_channels()) - 1
+    }
+
+    /// Перечисляет состояния системы, достижимые из пустой системы.
+    /// # Возвращаемое значение
+    /// Состояния в порядке нумерации строк матрицы Колмогорова, тип: `Vec<ChannelState>`.
+    pub fn states(&self) -> Vec<ChannelState> {
+        let empty = ChannelState { busy: 0, queue: 0, pointer: 0 };
+        markov_chain::reachable_states(empty, |state| self.transitions(state))
+    }
+
+    /// Варианты выбора канала для заявки, заставшей свободные каналы.
+    /// # Возвращаемое значение
+    /// Пары (номер канала, вероятность выбора), тип: `Vec<(usize, f64)>`.
+    fn assignments(&self, state: &ChannelState) -> Vec<(usize, f64)> {
+        let s = self.num_channels();
+        let idle: Vec<usize> = (0..s).filter(|i| state.busy & (1 << i) == 0).collect();
+
+        match self.policy {
+            AssignmentPolicy::FastestFreeFirst => {
+                let fastest = idle.iter()
+                    .copied()
+                    .fold(idle[0], |best, i| if self.mu_rates[i] > self.mu_rates[best] { i } else { best });
+                vec![(fastest, 1.0)]
+            }
+            AssignmentPolicy::Random => idle.iter().map(|&i| (i, 1.0 / idle.len() as f64)).collect(),
+            AssignmentPolicy::RoundRobin => {
+                let next = (0..s)
+                    .map(|offset| (state.pointer + offset) % s)
+                    .find(|i| state.busy & (1 << i) == 0)
+                    .expect("state with idle channels");
+                vec![(next, 1.0)]
+            }
+        }
+    }
+
+    /// Перечисляет переходы из состояния.
+    /// # Возвращаемое значение
+    /// Пары (новое состояние, интенсивность перехода), тип: `Vec<(ChannelState, f64)>`.
+    fn transitions(&self, state: &ChannelState) -> Vec<(ChannelState, f64)> {
+        let s = self.num_channels();
+        let mut transitions = Vec::new();
+
+        // Поступление заявки
+        if state.busy != self.all_busy() {
+            for (channel, probability) in self.assignments(state) {
+                let pointer = match self.policy {
+                    AssignmentPolicy::RoundRobin => (channel + 1) % s,
+                    _ => state.pointer,
+                };
+                transitions.push((
+                    ChannelState { busy: state.busy | (1 << channel), queue: 0, pointer },
+                    self.lambda_rate * probability,
+                ));
+            }
+        } else if state.queue < self.queue_size {
+            transitions.push((ChannelState { queue: state.queue + 1, ..*state }, self.lambda_rate));
+        }
+
+        // Завершение обслуживания на канале i: освободившийся канал берёт заявку из очереди
+        for (channel, mu_rate) in self.mu_rates.iter().enumerate() {
+            if state.busy & (1 << channel) != 0 {
+                let next = if state.queue > 0 {
+                    ChannelState { queue: state.queue - 1, ..*state }
+                } else {
+                    ChannelState { busy: state.busy & !(1 << channel), ..*state }
+                };
+                transitions.push((next, *mu_rate));
+            }
+        }
+
+        transitions
+    }
+
+    /// Строит матрицу правых частей уравнений Колмогорова на пространстве состояний `states()`.
+    /// # Возвращаемое значение
+    /// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+    pub fn generate_kolmogorov_matrix(&self) -> DMatrix<f64> {
+        markov_chain::generator_matrix(&self.states(), |state| self.transitions(state))
+    }
+
+    /// Вычисляет стационарное распределение по состояниям `states()`.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний, тип: `Result<DVector<f64>, Box<dyn Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn Error>> {
+        markov_chain::stationary_distribution(&self.generate_kolmogorov_matrix())
+    }
+
+    /// Вычисляет коэффициент загрузки каждого канала.
+    /// # Возвращаемое значение
+    /// Вероятность того, что канал i занят, тип: `Result<Vec<f64>, Box<dyn Error>>`.
+    pub fn calculate_channel_utilisations(&self) -> Result<Vec<f64>, Box<dyn Error>> {
+        let states = self.states();
+        let distribution = self.stationary_distribution()?;
+
+        Ok((0..self.num_channels()).map(|channel| {
+            states.iter()
+                .zip(distribution.iter())
+                .filter(|(state, _)| state.busy & (1 << channel) != 0)
+                .map(|(_, p)| p)
+                .sum()
+        }).collect())
+    }
+
+    /// Сводит стационарное распределение к числу заявок в системе и вычисляет
+    /// агрегированные характеристики. Средняя интенсивность обслуживания канала
+    /// берётся как среднее μ_i, пропускная способность — как Σ μ_i·P(канал i занят).
+    /// # Возвращаемое значение
+    /// Характеристики СМО, тип: `Result<StationaryCharacteristics, Box<dyn Error>>`.
+    pub fn characteristics(&self) -> Result<StationaryCharacteristics, Box<dyn Error>> {
+        let s = self.num_channels();
+        let states = self.states();
+        let distribution = self.stationary_distribution()?;
+
+        let mut aggregated = DVector::zeros(s + self.queue_size as usize + 1);
+        for (state, p) in states.iter().zip(distribution.iter()) {
+            aggregated[state.customers()] += p;
+        }
+
+        let throughput = self.calculate_channel_utilisations()?.iter()
+            .zip(self.mu_rates.iter())
+            .map(|(utilisation, mu_rate)| utilisation * mu_rate)
+            .sum();
+        let mean_mu_rate = self.mu_rates.iter().sum::<f64>() / s as f64;
+
+        Ok(StationaryCharacteristics::new(
+            self.lambda_rate,
+            mean_mu_rate,
+            s as i32,
+            self.queue_size,
+            self.time,
+            aggregated,
+            throughput
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::queuing_system::QueuingSystem;
+    use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+    #[test]
+    fn identical_channels_reduce_to_birth_death_model() {
+        let homogeneous = QueuingSystem::new(3.0, 1.2, 0.0, None, None, 3, 4, Config::empty_initial_state(3, 4), 1, 100, 0.01)
+            .stationary_characteristics().unwrap();
+        for policy in [AssignmentPolicy::FastestFreeFirst, AssignmentPolicy::Random, AssignmentPolicy::RoundRobin] {
+            let characteristics = HeterogeneousQueuingSystem::new(3.0, vec![1.2; 3], 4, policy, 1).unwrap().characteristics().unwrap();
+            for i in 0..homogeneous.distribution.len() {
+                assert!((characteristics.distribution[i] - homogeneous.distribution[i]).abs() < 1e-9);
+            }
+            assert!((characteristics.calculate_average_time_in_system() - homogeneous.calculate_average_time_in_system()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn round_robin_states_are_reachable_from_the_empty_system() {
+        let system = HeterogeneousQueuingSystem::new(1.0, vec![1.0, 2.0, 3.0], 2, AssignmentPolicy::RoundRobin, 1).unwrap();
+        let states = system.states();
+        // s·(2^s - 1) состояний со свободными каналами и s·(n + 1) с полной занятостью
+        assert_eq!(states.len(), 3 * 7 + 3 * 3);
+        assert_eq!(states[0], ChannelState { busy: 0, queue: 0, pointer: 0 });
+    }
+
+    #[test]
+    fn rejects_invalid_channels() {
+        assert!(HeterogeneousQueuingSystem::new(1.0, vec![], 2, AssignmentPolicy::Random, 1).is_err());
+        assert!(HeterogeneousQueuingSystem::new(1.0, vec![1.0; MAX_CHANNELS + 1], 2, AssignmentPolicy::Random, 1).is_err());
+        assert!(HeterogeneousQueuingSystem::new(1.0, vec![1.0, 0.0], 2, AssignmentPolicy::Random, 1).is_err());
+        assert!(HeterogeneousQueuingSystem::new(1.0, vec![1.0, -2.0], 2, AssignmentPolicy::Random, 1).is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use nalgebra::DVector;
+
+use crate::plot_options::{Locale, PlotOptions};
+use crate::queuing_system::QueuingSystem;
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+use crate::report::{characteristic_descriptions, characteristics_report, model_parameters};
+
+/// Стили отчёта: отчёт не ссылается на внешние файлы и открывается в любом браузере.
+const STYLE: &str = r#"
+body { font-family: sans-serif; max-width: 1100px; margin: 0 auto; padding: 20px; color: #222; }
+h1, h2 { color: #1a3d6d; }
+table { border-collapse: collapse; margin: 10px 0; }
+th, td { border: 1px solid #bbb; padding: 6px 10px; text-align: left; vertical-align: top; }
+th { background: #eef2f7; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+code { white-space: nowrap; }
+.verdict { padding: 12px 16px; border-radius: 6px; margin: 10px 0; }
+.stable { background: #e5f5e5; border: 1px solid #5a5; }
+.critical { background: #fff5dc; border: 1px solid #d9a400; }
+.overloaded { background: #fbe3e3; border: 1px solid #c44; }
+.figure svg { max-width: 100%; height: auto; }
+.bar { background: #4a7bd0; height: 12px; }
+.note { color: #666; font-size: 0.9em; }
+"#;
+
+/// Ползунок времени: показывает распределение вероятностей состояний в выбранный момент.
+const SCRIPT: &str = r#"
+const slider = document.getElementById('time-slider');
+slider.max = REPORT_DATA.times.length - 1;
+function updateStates() {
+    const k = Number(slider.value);
+    document.getElementById('time-value').textContent = REPORT_DATA.times[k].toFixed(3);
+    document.getElementById('state-rows').innerHTML = REPORT_DATA.labels.map((label, i) => {
+        const p = REPORT_DATA.states[k][i];
+        return '<tr><td>' + label + '</td><td class="number">' + p.toFixed(4) + '</td>'
+            + '<td class="number">' + REPORT_DATA.stationary[i].toFixed(4) + '</td>'
+            + '<td style="width: 300px"><div class="bar" style="width: ' + (100 * p).toFixed(1) + '%"></div></td></tr>';
+    }).join('');
+}
+slider.addEventListener('input', updateStates);
+updateStates();
+"#;
+
+/// Экранирует специальные символы HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Записывает числа как массив JSON.
+fn json_array(values: &[f64]) -> String {
+    format!("[{}]", values.iter().map(|value| value.to_string()).collect::<Vec<String>>().join(","))
+}
+
+/// Вывод о нагрузке по коэффициенту использования ρ = λ/(sμ): класс оформления и пояснение.
+/// Очередь ограничена n местами, поэтому установившийся режим существует при любом ρ;
+/// ρ показывает, чем система за него платит: ожиданием или отказами.
+fn stability_verdict(system: &QueuingSystem, rejection_probability: f64) -> (&'static str, String) {
+    let utilization = system.utilization();
+    let (class, verdict) = if utilization < 0.8 {
+        ("stable", "Каналы справляются с потоком заявок с запасом: очередь остаётся короткой, отказы редки.")
+    } else if utilization < 1.0 {
+        ("critical", "Каналы работают близко к пределу: небольшой рост потока заявок заметно увеличит очередь, ожидание и долю отказов.")
+    } else {
+        ("overloaded", "Каналы перегружены: заявки поступают не медленнее, чем каналы успевают их обслуживать. \
+            При неограниченной очереди установившегося режима не было бы и очередь росла бы без конца; \
+            здесь её ограничивают места в очереди, поэтому очередь почти всегда заполнена, а избыток потока получает отказ.")
+    };
+    let abandonment = if system.abandonment_rate > 0.0 {
+        " Уходы из очереди (θ > 0) дополнительно сокращают её длину."
+    } else {
+        ""
+    };
+    (class, format!(
+        "<strong>ρ = λ/(sμ) = {:.3}, P_отк = {:.4}.</strong> Очередь ограничена n = {} местами, \
+            поэтому установившийся режим существует при любой нагрузке. {}{}",
+        utilization, rejection_probability, system.queue_size, verdict, abandonment
+    ))
+}
+
+/// Формирует самодостаточный HTML-отчёт о модели: параметры, граф состояний (SVG),
+/// вероятности состояний во времени (SVG и ползунок времени), таблица характеристик
+/// с формулами и пояснениями и вывод об устойчивости. Графики рисуются на русском языке,
+/// заголовки и подписи осей из настроек не применяются.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// * `states` - Пары (t, p(t)) траектории `system.integrate_system()`.
+/// * `options` - Настройки построения графиков.
+/// # Возвращаемое значение
+/// Текст HTML, тип: `Result<String, Box<dyn Error>>`.
+pub fn html_report(system: &QueuingSystem, states: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<String, Box<dyn Error>> {
+    let options = PlotOptions {
+        locale: Locale::Russian,
+        labels: HashMap::new(),
+        ..options.clone()
+    };
+    let state_graph_svg = system.state_graph_svg(&options)?;
+    let states_svg = system.states_svg(states, &options)?;
+
+    let parameter_rows: String = model_parameters(system).iter()
+        .map(|parameter| format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"number\">{}</td><td>{}</td></tr>\n",
+            parameter.name, parameter.symbol, parameter.value, parameter.unit
+        ))
+        .collect();
+
+    let characteristics = system.stationary_characteristics()?;
+    let characteristic_rows: String = characteristics_report(&characteristics).iter()
+        .zip(characteristic_descriptions())
+        .map(|((name, value), description)| format!(
+            "<tr><td>{}</td><td class=\"number\">{:.4}</td><td>{}</td><td><code>{}</code></td><td>{}</td></tr>\n",
+            escape_html(name), value, description.unit, escape_html(description.formula), escape_html(description.interpretation)
+        ))
+        .collect();
+    let formula_note = "<p class=\"note\">Характеристики вычислены по стационарным вероятностям P_i уравнений Колмогорова \
+        и учитывают ограничение очереди, уходы из очереди, отказ от присоединения и групповые поступления. \
+        X = Σ min(i, s)·μ·P_i — пропускная способность, λ_пр = X + θ·L_q — интенсивность потока принятых заявок \
+        (обслуженных и ушедших из очереди); при групповых поступлениях λ — интенсивность потока заявок λ·E[размер группы].</p>\n";
+
+    let (verdict_class, verdict) = stability_verdict(system, characteristics.calculate_rejection_probability());
+    let labels = system.state_labels();
+    let data = format!(
+        "const REPORT_DATA = {{ times: {}, labels: [{}], states: [{}], stationary: {} }};",
+        json_array(&states.iter().map(|(t, _)| *t).collect::<Vec<f64>>()),
+        labels.iter().map(|label| format!("\"{}\"", escape_html(label))).collect::<Vec<String>>().join(","),
+        states.iter().map(|(_, state)| json_array(state.as_slice())).collect::<Vec<String>>().join(","),
+        json_array(characteristics.distribution.as_slice())
+    );
+
+    Ok(format!(
+        "<!DOCTYPE html>
+<html lang=\"ru\">
+<head>
+<meta charset=\"utf-8\">
+<title>Отчёт о моделировании СМО</title>
+<style>{style}</style>
+</head>
+<body>
+<h1>Отчёт о моделировании СМО M/M/{s}/{n}</h1>
+<div class=\"verdict {verdict_class}\">{verdict}</div>
+
+<h2>Параметры модели</h2>
+<table>
+<tr><th>Параметр</th><th>Обозначение</th><th>Значение</th><th>Единица</th></tr>
+{parameter_rows}</table>
+
+<h2>Граф состояний</h2>
+<p>Состояние S_i — в системе i заявок. Подписи дуг — интенсивности переходов из уравнений Колмогорова.</p>
+<div class=\"figure\">{state_graph_svg}</div>
+
+<h2>Вероятности состояний во времени</h2>
+<p>Пунктирные линии — стационарные вероятности, к которым система приходит с течением времени.</p>
+<div class=\"figure\">{states_svg}</div>
+<p><label>Момент времени t = <span id=\"time-value\"></span>
+<input type=\"range\" id=\"time-slider\" min=\"0\" value=\"0\" style=\"width: 400px\"></label></p>
+<table>
+<tr><th>Состояние</th><th>p_i(t)</th><th>Стационарная</th><th></th></tr>
+<tbody id=\"state-rows\"></tbody>
+</table>
+
+<h2>Характеристики в установившемся режиме</h2>
+<table>
+<tr><th>Характеристика</th><th>Значение</th><th>Единица</th><th>Формула</th><th>Что означает</th></tr>
+{characteristic_rows}</table>
+{formula_note}
+<script>
+{data}
+{script}
+</script>
+</body>
+</html>
+",
+        style = STYLE,
+        s = system.num_channels,
+        n = system.queue_size,
+        verdict_class = verdict_class,
+        verdict = verdict,
+        parameter_rows = parameter_rows,
+        state_graph_svg = state_graph_svg,
+        states_svg = states_svg,
+        characteristic_rows = characteristic_rows,
+        formula_note = formula_note,
+        data = data,
+        script = SCRIPT,
+    ))
+}
+
+/// Сохраняет HTML-отчёт о модели в файл.
+/// # Параметры
+/// * `path` - Путь к HTML-файлу.
+/// * `system` - Модель СМО.
+/// * `states` - Пары (t, p(t)) траектории `system.integrate_system()`.
+/// * `options` - Настройки построения графиков.
+/// # Возвращаемое значение
+/// Результат записи файла, тип: `Result<(), Box<dyn Error>>`.
+pub fn export_html_report<P: AsRef<Path>>(path: P, system: &QueuingSystem, states: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<(), Box<dyn Error>> {
+    fs::write(path, html_report(system, states, options)?)?;
+    Ok(())
+}
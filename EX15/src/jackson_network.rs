@@ -0,0 +1,330 @@
+use std::collections::VecDeque;
+use std::error::Error;
+
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::ggs_queuing_system::GGSQueuingSystem;
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+/// Станция сети: многоканальная СМО с экспоненциальным обслуживанием.
+#[derive(Debug, Clone)]
+pub struct NetworkStation {
+    pub name: String,               // Название станции
+    pub num_channels: i32,          // Количество каналов
+    pub mu_rate: f64,               // Интенсивность обработки одним каналом
+    pub queue_size: Option<i32>,    // Ограничение на размер очереди, None — неограниченная очередь
+    pub external_arrival_rate: f64, // Интенсивность внешнего потока заявок на станцию
+}
+
+/// Характеристики одной станции сети.
+#[derive(Debug, Clone, Copy)]
+pub struct StationMetrics {
+    pub arrival_rate: f64,            // Интенсивность потока принятых заявок (пропускная способность станции)
+    pub utilisation: f64,             // Загрузка одного канала
+    pub rejection_probability: f64,   // Вероятность потери заявки из-за заполненной очереди
+    pub average_number_in_queue: f64, // Среднее число заявок в очереди
+    pub average_number_in_system: f64, // Среднее число заявок на станции
+    pub average_time_in_system: f64,  // Среднее время пребывания на станции за одно посещение
+}
+
+/// Сквозные характеристики сети.
+#[derive(Debug, Clone)]
+pub struct NetworkMetrics {
+    pub stations: Vec<StationMetrics>, // Характеристики станций
+    pub throughput: f64,               // Интенсивность потока заявок, покинувших сеть после обслуживания
+    pub loss_probability: f64,         // Вероятность потери заявки на какой-либо станции
+    pub average_sojourn_time: f64,     // Среднее время пребывания заявки в сети
+    pub bottleneck: usize,             // Номер станции с наибольшей загрузкой
+}
+
+/// Открытая сеть Джексона: заявки поступают на станции извне, после обслуживания
+/// на станции i переходят на станцию j с вероятностью routing[i][j]
+/// или покидают сеть с вероятностью 1 - Σ_j routing[i][j].
+pub struct JacksonNetwork {
+    pub stations: Vec<NetworkStation>, // Станции сети
+    pub routing: Vec<Vec<f64>>,        // Матрица вероятностей переходов между станциями
+}
+
+/// Заявка в имитационной модели сети.
+#[derive(Debug, Clone, Copy)]
+struct Customer {
+    entered_network: f64, // Момент поступления в сеть
+    entered_station: f64, // Момент поступления на текущую станцию
+}
+
+impl JacksonNetwork {
+    /// Создаёт открытую сеть.
+    /// # Параметры
+    /// * `stations` - Станции сети, хотя бы одна.
+    /// * `routing` - Квадратная матрица вероятностей переходов между станциями: неотрицательные,
+    ///   в сумме по строке не больше 1.
+    /// # Возвращаемое значение
+    /// Сеть либо ошибка при некорректных станциях или маршрутизации, тип: `Result<JacksonNetwork, Box<dyn Error>>`.
+    pub fn new(stations: Vec<NetworkStation>, routing: Vec<Vec<f64>>) -> Result<JacksonNetwork, Box<dyn Error>> {
+        let size = stations.len();
+        if size == 0 {
+            return Err("в сети нет ни одной станции".into());
+        }
+        if let Some(station) = stations.iter().find(|station| {
+            station.num_channels < 1 || !(station.mu_rate > 0.0 && station.mu_rate.is_finite())
+                || !(station.external_arrival_rate >= 0.0 && station.external_arrival_rate.is_finite())
+                || station.queue_size.is_some_and(|queue_size| queue_size < 0)
+        }) {
+            return Err(format!("некорректные параметры станции «{}»", station.name).into());
+        }
+        if routing.len() != size || routing.iter().any(|row| row.len() != size) {
+            return Err(format!("для {} станций нужна матрица маршрутизации {}×{}", size, size, size).into());
+        }
+        if routing.iter().flatten().any(|r| !r.is_finite() || *r < 0.0) {
+            return Err("вероятности переходов между станциями должны быть неотрицательными".into());
+        }
+        if let Some(station) = routing.iter().position(|row| row.iter().sum::<f64>() > 1.0 + 1e-9) {
+            return Err(format!("вероятности переходов со станции «{}» в сумме больше 1", stations[station].name).into());
+        }
+
+        Ok(JacksonNetwork {
+            stations,
+            routing
+        })
+    }
+
+    /// Решает уравнения трафика λ_j = γ_j + Σ_i λ_i·r_ij.
+    /// # Возвращаемое значение
+    /// Суммарные интенсивности потоков на станции, тип: `Result<Vec<f64>, Box<dyn Error>>`.
+    pub fn solve_traffic_equations(&self) -> Result<Vec<f64>, Box<dyn Error>> {
+        let size = self.stations.len();
+        let matrix = DMatrix::from_fn(size, size, |j, i| {
+            if i == j { 1.0 - self.routing[i][j] } else { -self.routing[i][j] }
+        });
+        let external = DVector::from_iterator(size, self.stations.iter().map(|station| station.external_arrival_rate));
+
+        let solution = matrix.lu()
+            .solve(&external)
+            .ok_or("уравнения трафика вырождены: заявки не могут покинуть сеть")?;
+        Ok(solution.iter().copied().collect())
+    }
+
+    /// Вычисляет характеристики сети с неограниченными очередями по мультипликативной форме:
+    /// каждая станция ведёт себя как независимая СМО M/M/s с потоком λ_j из уравнений трафика.
+    /// # Возвращаемое значение
+    /// Характеристики сети, тип: `Result<NetworkMetrics, Box<dyn Error>>`.
+    pub fn product_form_metrics(&self) -> Result<NetworkMetrics, Box<dyn Error>> {
+        if self.stations.iter().any(|station| station.queue_size.is_some()) {
+            return Err("мультипликативная форма применима только к станциям с неограниченной очередью".into());
+        }
+
+        let arrival_rates = self.solve_traffic_equations()?;
+        let stations = self.stations.iter()
+            .zip(arrival_rates.iter())
+            .map(|(station, &arrival_rate)| {
+                let system = GGSQueuingSystem::markovian(arrival_rate, station.mu_rate, station.num_channels, 1);
+                let utilisation = system.calculate_utilisation();
+                if utilisation >= 1.0 {
+                    return Err(format!("станция «{}» перегружена: ρ = {:.4}", station.name, utilisation).into());
+                }
+
+                Ok(StationMetrics {
+                    arrival_rate,
+                    utilisation,
+                    rejection_probability: 0.0,
+                    average_number_in_queue: system.calculate_average_number_of_requests_in_queue(),
+                    average_number_in_system: system.calculate_total_number_of_requests(),
+                    average_time_in_system: system.calculate_average_time_in_system(),
+                })
+            })
+            .collect::<Result<Vec<StationMetrics>, Box<dyn Error>>>()?;
+
+        let throughput: f64 = self.stations.iter().map(|station| station.external_arrival_rate).sum();
+        let number_in_network: f64 = stations.iter().map(|metrics| metrics.average_number_in_system).sum();
+
+        Ok(NetworkMetrics {
+            bottleneck: Self::bottleneck(&stations),
+            stations,
+            throughput,
+            loss_probability: 0.0,
+            average_sojourn_time: number_in_network / throughput,
+        })
+    }
+
+    /// Имитационное моделирование сети. Заявка, заставшая очередь станции заполненной, теряется.
+    /// Статистика собирается после периода разогрева.
+    /// # Параметры
+    /// * `horizon` - Продолжительность моделирования.
+    /// * `warm_up` - Продолжительность периода разогрева.
+    /// * `seed` - Начальное значение генератора случайных чисел.
+    /// # Возвращаемое значение
+    /// Оценки характеристик сети, тип: `NetworkMetrics`.
+    pub fn simulate(&self, horizon: f64, warm_up: f64, seed: u64) -> NetworkMetrics {
+        let size = self.stations.len();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        // Заявки на станции в порядке поступления, первые min(длина, s) обслуживаются
+        let mut queues: Vec<VecDeque<Customer>> = vec![VecDeque::new(); size];
+
+        let mut arrivals = vec![0usize; size];
+        let mut losses = vec![0usize; size];
+        let mut departures = vec![0usize; size];
+        let mut total_time_at_station = vec![0.0; size];
+        let mut queue_area = vec![0.0; size];
+        let mut system_area = vec![0.0; size];
+        let mut busy_area = vec![0.0; size];
+        let mut external_arrivals = 0usize;
+        let mut lost_customers = 0usize;
+        let mut completed_customers = 0usize;
+        let mut total_sojourn_time = 0.0;
+
+        let external_rate: f64 = self.stations.iter().map(|station| station.external_arrival_rate).sum();
+
+        let mut t = 0.0;
+        while t < horizon {
+            let busy: Vec<usize> = queues.iter()
+                .zip(self.stations.iter())
+                .map(|(queue, station)| queue.len().min(station.num_channels as usize))
+                .collect();
+            let service_rates: Vec<f64> = busy.iter()
+                .zip(self.stations.iter())
+                .map(|(&busy, station)| busy as f64 * station.mu_rate)
+                .collect();
+            let total_rate = external_rate + service_rates.iter().sum::<f64>();
+
+            let dt = -rng.gen::<f64>().ln() / total_rate;
+            if t + dt > warm_up {
+                let observed = (t + dt).min(horizon) - t.max(warm_up);
+                for i in 0..size {
+                    queue_area[i] += (queues[i].len() - busy[i]) as f64 * observed;
+                    system_area[i] += queues[i].len() as f64 * observed;
+                    busy_area[i] += busy[i] as f64 * observed;
+                }
+            }
+            t += dt;
+            let measured = t > warm_up;
+
+            let mut event = rng.gen::<f64>() * total_rate;
+            let external_station = self.stations.iter().position(|station| {
+                event -= station.external_arrival_rate;
+                event < 0.0
+            });
+
+            // Станция, на которую поступает заявка, и сама заявка
+            let (target, customer) = match external_station {
+                Some(station) => {
+                    if measured {
+                        external_arrivals += 1;
+                    }
+                    (Some(station), Customer { entered_network: t, entered_station: t })
+                }
+                None => {
+                    let station = service_rates.iter().position(|rate| {
+                        event -= rate;
+                        event < 0.0
+                    }).unwrap_or(size - 1);
+                    if busy[station] == 0 {
+                        continue;
+                    }
+
+                    let finished = queues[station].remove(rng.gen_range(0..busy[station])).expect("busy channel");
+                    if measured {
+                        departures[station] += 1;
+                        total_time_at_station[station] += t - finished.entered_station;
+                    }
+
+                    let mut route = rng.gen::<f64>();
+                    let next = self.routing[station].iter().position(|&probability| {
+                        route -= probability;
+                        route < 0.0
+                    });
+                    if next.is_none() && measured && finished.entered_network > warm_up {
+                        completed_customers += 1;
+                        total_sojourn_time += t - finished.entered_network;
+                    }
+                    (next, Customer { entered_station: t, ..finished })
+                }
+            };
+
+            if let Some(station) = target {
+                if measured {
+                    arrivals[station] += 1;
+                }
+                let capacity = self.stations[station].queue_size
+                    .map_or(usize::MAX, |queue_size| (self.stations[station].num_channels + queue_size) as usize);
+                if queues[station].len() < capacity {
+                    queues[station].push_back(customer);
+                } else if measured {
+                    losses[station] += 1;
+                    lost_customers += 1;
+                }
+            }
+        }
+
+        let observed_time = horizon - warm_up;
+        let stations: Vec<StationMetrics> = (0..size).map(|i| {
+            let accepted_rate = (arrivals[i] - losses[i]) as f64 / observed_time;
+            StationMetrics {
+                arrival_rate: accepted_rate,
+                utilisation: busy_area[i] / (self.stations[i].num_channels as f64 * observed_time),
+                rejection_probability: losses[i] as f64 / arrivals[i].max(1) as f64,
+                average_number_in_queue: queue_area[i] / observed_time,
+                average_number_in_system: system_area[i] / observed_time,
+                average_time_in_system: total_time_at_station[i] / departures[i].max(1) as f64,
+            }
+        }).collect();
+
+        NetworkMetrics {
+            bottleneck: Self::bottleneck(&stations),
+            stations,
+            throughput: completed_customers as f64 / observed_time,
+            loss_probability: lost_customers as f64 / external_arrivals.max(1) as f64,
+            average_sojourn_time: total_sojourn_time / completed_customers.max(1) as f64,
+        }
+    }
+
+    /// Находит станцию с наибольшей загрузкой канала.
+    fn bottleneck(stations: &[StationMetrics]) -> usize {
+        stations.iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.utilisation.total_cmp(&b.utilisation))
+            .map_or(0, |(i, _)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn station(name: &str, num_channels: i32, mu_rate: f64, external_arrival_rate: f64) -> NetworkStation {
+        NetworkStation { name: name.to_string(), num_channels, mu_rate, queue_size: None, external_arrival_rate }
+    }
+
+    #[test]
+    fn product_form_matches_simulation() {
+        // После первой станции половина заявок уходит на вторую, со второй 30% возвращаются на первую
+        let network = JacksonNetwork::new(
+            vec![station("A", 1, 3.0, 1.0), station("B", 2, 1.0, 0.5)],
+            vec![vec![0.0, 0.5], vec![0.3, 0.0]],
+        ).unwrap();
+        let exact = network.product_form_metrics().unwrap();
+        let simulated = network.simulate(200_000.0, 1_000.0, 7);
+
+        // λ_A = 1 + 0.3·λ_B, λ_B = 0.5 + 0.5·λ_A
+        assert!((exact.stations[0].arrival_rate - 1.15 / 0.85).abs() < 1e-9);
+        assert!((exact.throughput - simulated.throughput).abs() / exact.throughput < 0.03);
+        assert!((exact.average_sojourn_time - simulated.average_sojourn_time).abs() / exact.average_sojourn_time < 0.05);
+        for (exact, simulated) in exact.stations.iter().zip(simulated.stations.iter()) {
+            assert!((exact.arrival_rate - simulated.arrival_rate).abs() / exact.arrival_rate < 0.03);
+            assert!((exact.average_number_in_system - simulated.average_number_in_system).abs() / exact.average_number_in_system < 0.05);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_networks() {
+        assert!(JacksonNetwork::new(vec![], vec![]).is_err());
+        // Матрица маршрутизации не квадратная
+        assert!(JacksonNetwork::new(vec![station("A", 1, 1.0, 1.0)], vec![vec![0.0, 0.0]]).is_err());
+        // Отрицательная вероятность и сумма по строке больше 1
+        let stations = vec![station("A", 1, 1.0, 1.0), station("B", 1, 1.0, 0.0)];
+        assert!(JacksonNetwork::new(stations.clone(), vec![vec![0.0, -0.1], vec![0.0, 0.0]]).is_err());
+        assert!(JacksonNetwork::new(stations, vec![vec![0.6, 0.6], vec![0.0, 0.0]]).is_err());
+    }
+}
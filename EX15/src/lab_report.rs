@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use nalgebra::DVector;
+
+use crate::plot_options::{ImageFormat, Locale, PlotOptions};
+use crate::queuing_system::QueuingSystem;
+use crate::report::{characteristic_descriptions, characteristics_report, model_parameters};
+use crate::state_graph::format_rate;
+
+/// Формат отчёта для оформления практического занятия.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Markdown,
+    Latex,
+}
+
+impl ReportFormat {
+    /// Разбирает формат отчёта из аргумента командной строки `--lab-report=md|tex`.
+    /// # Параметры
+    /// * `args` - Аргументы командной строки.
+    /// # Возвращаемое значение
+    /// Формат отчёта, `None`, если отчёт не запрошен, либо ошибка для неизвестного формата,
+    /// тип: `Result<Option<ReportFormat>, Box<dyn Error>>`.
+    pub fn from_args(args: &[String]) -> Result<Option<ReportFormat>, Box<dyn Error>> {
+        let mut format = None;
+        for value in args.iter().filter_map(|arg| arg.strip_prefix("--lab-report=")) {
+            format = Some(match value {
+                "md" => ReportFormat::Markdown,
+                "tex" => ReportFormat::Latex,
+                _ => return Err(format!("неизвестный формат отчёта: {}", value).into()),
+            });
+        }
+        Ok(format)
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            ReportFormat::Markdown => "md",
+            ReportFormat::Latex => "tex",
+        }
+    }
+}
+
+/// Рисунок отчёта: подпись и имя файла относительно каталога отчёта.
+#[derive(Debug, Clone)]
+pub struct FigureReference {
+    pub caption: &'static str,
+    pub file: String,
+}
+
+/// Пояснение к таблице характеристик: обозначения формул `characteristic_descriptions`.
+/// Тире записывается как «—» в Markdown и как «---» в LaTeX.
+fn characteristics_note(dash: &str) -> String {
+    format!(
+        "Характеристики вычислены по стационарным вероятностям $P_i$ ($A P = 0$, $\\sum_i P_i = 1$) \
+            и учитывают ограничение очереди $n$ местами; $X = \\sum_i \\min(i, s) \\mu P_i$ {} пропускная способность, \
+            $\\lambda_{{\\text{{пр}}}} = X + \\theta L_q$ {} интенсивность потока принятых заявок (обслуженных и ушедших из очереди).",
+        dash, dash
+    )
+}
+
+/// Запись обозначения интенсивности в LaTeX.
+fn latex_symbol(symbol: &str) -> &str {
+    match symbol {
+        "λ" => r"\lambda",
+        "μ" => r"\mu",
+        "θ" => r"\theta",
+        other => other,
+    }
+}
+
+/// Коэффициент уравнения Колмогорова для S_row при p_col в записи LaTeX: «\lambda», «3\mu»
+/// или «(3\mu + \theta)». Для диагонального элемента возвращается интенсивность выхода из состояния,
+/// которая входит в уравнение со знаком минус. Пустой коэффициент означает отсутствие перехода.
+fn latex_coefficient(components: &[(&'static str, Vec<Vec<f64>>)], row: usize, col: usize) -> Option<String> {
+    let sign = if row == col { -1.0 } else { 1.0 };
+    let terms: Vec<String> = components.iter()
+        .map(|(symbol, matrix)| (latex_symbol(symbol), sign * matrix[row][col]))
+        .filter(|&(_, coefficient)| coefficient != 0.0)
+        .map(|(symbol, coefficient)| {
+            if coefficient == 1.0 { symbol.to_string() } else { format!("{}{}", format_rate(coefficient), symbol) }
+        })
+        .collect();
+    match terms.len() {
+        0 => None,
+        1 => Some(terms[0].clone()),
+        _ => Some(format!("({})", terms.join(" + "))),
+    }
+}
+
+/// Записывает уравнения Колмогорова dp_i/dt = Σ_j A[i][j]·p_j в символьном виде
+/// по разложению матрицы `generate_kolmogorov_matrix` на слагаемые с λ, μ и θ.
+/// Строки предназначены для окружения `align*` (или `aligned`) и выравниваются по знаку равенства.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// # Возвращаемое значение
+/// Уравнения по одному на состояние, тип: `Vec<String>`.
+pub fn symbolic_kolmogorov_equations(system: &QueuingSystem) -> Vec<String> {
+    let components = system.kolmogorov_matrix_components();
+    let number_of_states = system.generate_kolmogorov_matrix().len();
+
+    (0..number_of_states).map(|i| {
+        let terms: Vec<String> = (0..number_of_states)
+            .filter_map(|j| latex_coefficient(&components, i, j).map(|coefficient| {
+                let sign = if i == j { "-" } else { "+" };
+                format!("{} {} p_{{{}}}", sign, coefficient, j)
+            }))
+            .collect();
+        let right_side = terms.join(" ");
+        let right_side = right_side.strip_prefix("+ ").unwrap_or(&right_side);
+        format!(r"\frac{{dp_{{{}}}}}{{dt}} &= {}", i, if right_side.is_empty() { "0" } else { right_side })
+    }).collect()
+}
+
+/// Начальные условия p_i(0) в записи LaTeX.
+fn latex_initial_conditions(system: &QueuingSystem) -> String {
+    system.initial_state.iter()
+        .enumerate()
+        .map(|(i, (_, value))| format!("p_{{{}}}(0) = {}", i, value))
+        .collect::<Vec<String>>()
+        .join(r",\; ")
+}
+
+/// Числовая матрица уравнений Колмогорова в записи LaTeX.
+fn latex_matrix(system: &QueuingSystem) -> String {
+    let rows: Vec<String> = system.generate_kolmogorov_matrix().iter()
+        .map(|row| row.iter().map(|&value| format_rate(value)).collect::<Vec<String>>().join(" & "))
+        .collect();
+    format!("A = \\begin{{pmatrix}}\n{}\n\\end{{pmatrix}}", rows.join(" \\\\\n"))
+}
+
+/// Символ параметра модели в записи LaTeX.
+fn latex_parameter_symbol(symbol: &str) -> String {
+    format!("${}$", latex_symbol(symbol))
+}
+
+/// Формирует отчёт в формате Markdown: математика записывается в LaTeX между `$`,
+/// как в ячейках Jupyter-блокнота.
+fn markdown_report(system: &QueuingSystem, figures: &[FigureReference]) -> Result<String, Box<dyn Error>> {
+    let mut report = format!("# Отчёт о моделировании СМО M/M/{}/{}\n\n", system.num_channels, system.queue_size);
+
+    report += "## Исходные данные\n\n| Параметр | Обозначение | Значение | Единица |\n|---|---|---:|---|\n";
+    for parameter in model_parameters(system) {
+        report += &format!(
+            "| {} | {} | {} | {} |\n",
+            parameter.name, latex_parameter_symbol(parameter.symbol), parameter.value, parameter.unit
+        );
+    }
+    report += &format!(
+        "\nКоэффициент использования каналов: $\\rho = \\lambda / (s\\mu) = {:.3}$.\n\n",
+        system.utilization()
+    );
+
+    report += "## Уравнения Колмогорова\n\n";
+    report += &format!(
+        "$$\n\\begin{{aligned}}\n{}\n\\end{{aligned}}\n$$\n\nНачальные условия: ${}$.\n\n",
+        symbolic_kolmogorov_equations(system).join(" \\\\\n"),
+        latex_initial_conditions(system)
+    );
+    report += "Матрица правых частей, $\\frac{dp}{dt} = A p$ ($A_{ij}$ — интенсивность перехода из $S_j$ в $S_i$):\n\n";
+    report += &format!("$$\n{}\n$$\n\n", latex_matrix(system));
+
+    report += &format!("## Характеристики СМО\n\n{}\n\n", characteristics_note("—"));
+    report += "| Характеристика | Формула | Значение | Единица |\n|---|---|---:|---|\n";
+    for ((name, value), description) in characteristics_report(&system.stationary_characteristics()?).iter().zip(characteristic_descriptions()) {
+        report += &format!("| {} | ${}$ | {:.4} | {} |\n", name, description.latex, value, description.unit);
+    }
+
+    report += "\n## Рисунки\n\n";
+    for (number, figure) in figures.iter().enumerate() {
+        report += &format!("![{}]({})\n\n*Рисунок {}. {}*\n\n", figure.caption, figure.file, number + 1, figure.caption);
+    }
+
+    Ok(report)
+}
+
+/// Формирует отчёт в формате LaTeX: полный документ для pdflatex с пакетами для русского языка.
+fn latex_report(system: &QueuingSystem, figures: &[FigureReference]) -> Result<String, Box<dyn Error>> {
+    let mut report = String::from(
+        "\\documentclass[a4paper,12pt]{article}\n\
+         \\usepackage[utf8]{inputenc}\n\
+         \\usepackage[T2A]{fontenc}\n\
+         \\usepackage[russian]{babel}\n\
+         \\usepackage{amsmath}\n\
+         \\usepackage{graphicx}\n"
+    );
+    // По умолчанию pmatrix ограничена 10 столбцами
+    report += &format!(
+        "\\setcounter{{MaxMatrixCols}}{{{}}}\n\n\\begin{{document}}\n\n",
+        system.generate_kolmogorov_matrix().len().max(10)
+    );
+    report += &format!("\\section*{{Отчёт о моделировании СМО M/M/{}/{}}}\n\n", system.num_channels, system.queue_size);
+
+    report += "\\subsection*{Исходные данные}\n\n\\begin{tabular}{|p{7cm}|c|r|l|}\n\\hline\nПараметр & Обозначение & Значение & Единица \\\\\n\\hline\n";
+    for parameter in model_parameters(system) {
+        report += &format!(
+            "{} & {} & {} & {} \\\\\n",
+            parameter.name, latex_parameter_symbol(parameter.symbol), parameter.value, parameter.unit
+        );
+    }
+    report += "\\hline\n\\end{tabular}\n\n";
+    report += &format!(
+        "Коэффициент использования каналов: $\\rho = \\lambda / (s\\mu) = {:.3}$.\n\n",
+        system.utilization()
+    );
+
+    report += "\\subsection*{Уравнения Колмогорова}\n\n";
+    report += &format!(
+        "\\begin{{align*}}\n{}\n\\end{{align*}}\n\nНачальные условия: ${}$.\n\n",
+        symbolic_kolmogorov_equations(system).join(" \\\\\n"),
+        latex_initial_conditions(system)
+    );
+    report += "Матрица правых частей, $\\frac{dp}{dt} = A p$ ($A_{ij}$ --- интенсивность перехода из $S_j$ в $S_i$):\n";
+    report += &format!("\\[\n{}\n\\]\n\n", latex_matrix(system));
+
+    report += &format!("\\subsection*{{Характеристики СМО}}\n\n{}\n\n", characteristics_note("---"));
+    report += "\\resizebox{\\textwidth}{!}{\\begin{tabular}{|p{6cm}|l|r|l|}\n\\hline\nХарактеристика & Формула & Значение & Единица \\\\\n\\hline\n";
+    for ((name, value), description) in characteristics_report(&system.stationary_characteristics()?).iter().zip(characteristic_descriptions()) {
+        report += &format!("{} & ${}$ & {:.4} & {} \\\\\n", name, description.latex, value, description.unit);
+    }
+    report += "\\hline\n\\end{tabular}}\n\n";
+
+    for figure in figures {
+        report += &format!(
+            "\\begin{{figure}}[h]\n\\centering\n\\includegraphics[width=\\textwidth]{{{}}}\n\\caption{{{}}}\n\\end{{figure}}\n\n",
+            figure.file, figure.caption
+        );
+    }
+
+    report += "\\end{document}\n";
+    Ok(report)
+}
+
+/// Формирует отчёт о модели для оформления практического занятия: исходные данные,
+/// уравнения Колмогорова в символьном виде, числовая матрица, таблица характеристик
+/// с формулами и единицами измерения и ссылки на рисунки.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// * `figures` - Рисунки, на которые ссылается отчёт.
+/// * `format` - Формат отчёта.
+/// # Возвращаемое значение
+/// Текст отчёта, тип: `Result<String, Box<dyn Error>>`.
+pub fn lab_report(system: &QueuingSystem, figures: &[FigureReference], format: ReportFormat) -> Result<String, Box<dyn Error>> {
+    match format {
+        ReportFormat::Markdown => markdown_report(system, figures),
+        ReportFormat::Latex => latex_report(system, figures),
+    }
+}
+
+/// Строит рисунки отчёта и сохраняет отчёт `queuing_system_report.{md,tex}` в каталог графиков,
+/// чтобы отчёт ссылался на рисунки по имени файла. Рисунки подписываются на русском языке
+/// и всегда сохраняются в PNG: `\includegraphics` не вставляет SVG.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// * `states` - Пары (t, p(t)) траектории `system.integrate_system()`.
+/// * `options` - Настройки построения графиков; каталог задаёт место сохранения отчёта.
+/// * `format` - Формат отчёта.
+/// # Возвращаемое значение
+/// Путь к файлу отчёта, тип: `Result<PathBuf, Box<dyn Error>>`.
+pub fn export_lab_report(system: &QueuingSystem, states: &[(f64, DVector<f64>)], options: &PlotOptions, format: ReportFormat) -> Result<PathBuf, Box<dyn Error>> {
+    let options = PlotOptions {
+        format: ImageFormat::Png,
+        locale: Locale::Russian,
+        labels: HashMap::new(),
+        ..options.clone()
+    };
+    system.plot_state_graph(&options)?;
+    system.plot_states(states, &options)?;
+    system.transient_metrics(states).plot(&options)?;
+
+    let file_name = |name: &str| options.output_path(name)
+        .file_name()
+        .map_or_else(String::new, |file| file.to_string_lossy().into_owned());
+    let figures = [
+        ("Граф состояний СМО", "queuing_system_states"),
+        ("Вероятности состояний во времени", "channels_states"),
+        ("Характеристики СМО во времени", "transient_metrics"),
+    ].map(|(caption, name)| FigureReference { caption, file: file_name(name) });
+
+    let path = options.directory.join(format!("queuing_system_report.{}", format.extension()));
+    fs::write(&path, lab_report(system, &figures, format)?)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_format_from_args() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<String>>();
+
+        assert_eq!(ReportFormat::from_args(&args(&["--plot"])).unwrap(), None);
+        assert_eq!(ReportFormat::from_args(&args(&["--lab-report=tex"])).unwrap(), Some(ReportFormat::Latex));
+        assert!(ReportFormat::from_args(&args(&["--lab-report=pdf"])).is_err());
+    }
+}
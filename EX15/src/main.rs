@@ -1,28 +1,123 @@
-
 use std::sync::Arc;
-use crate::config::QUEUING_SYSTEM_CONFIG;
+use nalgebra::DVector;
+use crate::abandonment_characteristics::AbandonmentCharacteristics;
+use crate::balking_characteristics::BalkingCharacteristics;
+use crate::batch_arrivals::{BatchArrivals, BatchRejectionPolicy};
+use crate::closed_network::{ClosedNetwork, ClosedStation};
+use crate::config::{Config, BATCH_SIZE_PROBABILITIES, CLOSED_NETWORK_CONFIG, FINITE_SOURCE_CONFIG, GGS_CONFIG, HETEROGENEOUS_CONFIG, NETWORK_CONFIG, PHASE_TYPE_CONFIG, PRIORITY_CONFIG, QUEUING_SYSTEM_CONFIG, RELIABILITY_CONFIG, RETRIAL_CONFIG, REWARD_CONFIG, SINGLE_CHANNEL_CONFIG, UNCERTAINTY_CONFIG};
+use crate::estimation::{Observations, ParameterEstimates};
+use crate::finite_source_queuing_system::FiniteSourceQueuingSystem;
+use crate::html_report::export_html_report;
+use crate::ggs_queuing_system::{GGSApproximation, GGSQueuingSystem};
+use crate::heterogeneous_queuing_system::{AssignmentPolicy, HeterogeneousQueuingSystem};
+use crate::jackson_network::{JacksonNetwork, NetworkStation};
+use crate::lab_report::{export_lab_report, ReportFormat};
+use crate::mg1_queuing_system::MG1QueuingSystem;
+use crate::phase_type_queuing_system::{PhaseTypeDistribution, PhaseTypeQueuingSystem};
+use crate::plot_options::PlotOptions;
+use crate::priority_queuing_system::{PriorityClass, PriorityDiscipline, PriorityQueuingSystem};
 use crate::queuing_system::QueuingSystem;
 use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+use crate::rate_distribution::RateDistribution;
+use crate::report::{characteristics_report, characteristics_report_with_intervals, export_comparison_csv, format_comparison_table, queuing_system_report};
+use crate::retrial_queuing_system::RetrialQueuingSystem;
+use crate::sensitivity::{characteristics_sensitivity, format_tornado_table, plot_tornado, SensitivityParameter};
+use crate::uncertainty::propagate_uncertainty;
+use crate::unreliable_queuing_system::UnreliableQueuingSystem;
 
+mod abandonment_characteristics;
+mod balking_characteristics;
+mod batch_arrivals;
+mod closed_network;
 mod config;
+mod estimation;
+mod finite_source_queuing_system;
+mod ggs_queuing_system;
+mod heterogeneous_queuing_system;
+mod html_report;
+mod jackson_network;
+mod lab_report;
+mod markov_chain;
+mod markov_reward;
+mod mg1_queuing_system;
+mod phase_type_queuing_system;
+mod plot_options;
+mod priority_queuing_system;
 mod queuing_system;
 mod queuing_system_characteristics;
+mod rate_distribution;
+mod report;
+mod retrial_queuing_system;
+mod sensitivity;
+mod state_graph;
+mod stationary_characteristics;
+mod statistics;
+mod transient_metrics;
+mod uncertainty;
+mod unreliable_queuing_system;
+
 
+/// Сообщает о некорректном аргументе командной строки и завершает программу с ненулевым кодом.
+fn exit_with_usage_error(error: Box<dyn std::error::Error>) -> ! {
+    eprintln!("Некорректные аргументы командной строки: {}", error);
+    std::process::exit(2)
+}
 
 fn main() {
+    // Аргументы: [путь к CSV с наблюдениями] [--plot] [--export] [--html-report[=PATH]] [--lab-report=md|tex]
+    // [настройки графиков, см. PlotOptions::from_args]
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let plot = args.iter().any(|arg| arg == "--plot");
+    let export = args.iter().any(|arg| arg == "--export");
+    let html_report_path = args.iter().find_map(|arg| {
+        if arg == "--html-report" { Some("queuing_system_report.html") } else { arg.strip_prefix("--html-report=") }
+    });
+    let lab_report_format = ReportFormat::from_args(&args).unwrap_or_else(|e| exit_with_usage_error(e));
+    let observations_path = args.iter().find(|arg| !arg.starts_with("--"));
+    let plot_options = PlotOptions::from_args(&args).unwrap_or_else(|e| exit_with_usage_error(e));
+
+    let observations = observations_path.map(|path| Observations::from_csv(path).expect("Failed to read observations"));
+    let estimates = observations.as_ref().map(|observations| {
+        ParameterEstimates::from_observations(observations, 0.95).expect("Failed to estimate parameters")
+    });
+    let estimated_config = estimates.as_ref().map(|estimates| estimates.to_config(&QUEUING_SYSTEM_CONFIG));
+    let config: &Config = estimated_config.as_ref().unwrap_or(&QUEUING_SYSTEM_CONFIG);
+
+    if let Some(estimates) = &estimates {
+        for (name, estimate) in [("λ", &estimates.lambda), ("μ", &estimates.mu)] {
+            println!(
+                "Оценка {} = {:.4}, доверительный интервал {:.0}%: [{:.4}; {:.4}], объём выборки: {}",
+                name, estimate.rate, estimates.confidence_level * 100.0, estimate.lower, estimate.upper, estimate.sample_size
+            );
+            println!(
+                "  Критерий Колмогорова–Смирнова: D = {:.4}, p: {:.4}; критерий χ²: χ² = {:.4}, p: {:.4}",
+                estimate.kolmogorov_smirnov.statistic, estimate.kolmogorov_smirnov.p_value,
+                estimate.chi_squared.statistic, estimate.chi_squared.p_value
+            );
+        }
+    }
 
     let queuing_system = QueuingSystem::new(
-        QUEUING_SYSTEM_CONFIG.lambda_rate,
-        QUEUING_SYSTEM_CONFIG.mu_rate,
-        QUEUING_SYSTEM_CONFIG.num_channels,
-        QUEUING_SYSTEM_CONFIG.queue_size,
-        Arc::clone(&QUEUING_SYSTEM_CONFIG.initial_state),
-        QUEUING_SYSTEM_CONFIG.time,
-        QUEUING_SYSTEM_CONFIG.num_iterations,
-        QUEUING_SYSTEM_CONFIG.step_size
-    );
-    
-    //queuing_system.plot_state_graph().expect("Failed to plot state graph");
+        config.lambda_rate,
+        config.mu_rate,
+        config.abandonment_rate,
+        config.balking,
+        config.batch_arrivals.clone(),
+        config.num_channels,
+        config.queue_size,
+        Arc::clone(&config.initial_state),
+        config.time,
+        config.num_iterations,
+        config.step_size
+    );
+
+    if plot {
+        queuing_system.plot_state_graph(&plot_options).expect("Failed to plot state graph");
+        queuing_system.state_graph().plot("queuing_system_state_graph", &plot_options).expect("Failed to render state graph");
+    }
+    if export {
+        queuing_system.state_graph().export_dot("queuing_system_states.dot").expect("Failed to export state graph");
+    }
 
     let matrix = queuing_system.generate_kolmogorov_matrix();
     println!("Правые части уравнений Колмогорова: {:?}", matrix);
@@ -30,34 +125,495 @@ fn main() {
     let states = queuing_system.integrate_system();
     println!("{:#?}", states);
 
-    //queuing_system.plot_states(states).expect("Failed to plot states");
-
-    let load_factor = queuing_system.calculate_load_factor();
-    let probability_of_downtime = queuing_system.calculate_probability_of_downtime();
-    let probabilities = queuing_system.calculate_probabilities();
-    let queue_probabilities = queuing_system.calculate_queue_probabilities();
-    let rejection_probability = queuing_system.calculate_rejection_probability();
-    let average_incoming_requests_during_t = queuing_system.calculate_average_incoming_requests_during_t();
-    let average_service_time_per_request = queuing_system.calculate_average_service_time_per_request();
-    let average_busy_channels = queuing_system.calculate_average_busy_channels();
-    let average_number_of_requests_in_queue = queuing_system.calculate_average_number_of_requests_in_queue();
-    let average_waiting_time_in_queue = queuing_system.calculate_average_waiting_time_in_queue();
-    let total_number_of_requests = queuing_system.calculate_total_number_of_requests();
-    let average_waiting_time = queuing_system.calculate_average_waiting_time();
-    let average_time_in_system = queuing_system.calculate_average_time_in_system();
-
-
-    println!("Коэффициент загрузки СМО: {}", load_factor);
-    println!("Вероятность простоя системы: {}", probability_of_downtime);
+    if let Some(path) = html_report_path {
+        export_html_report(path, &queuing_system, &states, &plot_options).expect("Failed to export HTML report");
+        println!("HTML-отчёт сохранён в {}", path);
+    }
+    if let Some(format) = lab_report_format {
+        let path = export_lab_report(&queuing_system, &states, &plot_options, format).expect("Failed to export lab report");
+        println!("Отчёт сохранён в {}", path.display());
+    }
+
+    // Затраты смены: занятые офицеры и штраф за ожидание заявок
+    let cost_rates = queuing_system.staffing_cost_rates(REWARD_CONFIG.busy_channel_cost, REWARD_CONFIG.waiting_cost);
+    let instantaneous_cost = markov_reward::instantaneous_reward(&states, &cost_rates);
+    let accumulated_cost = markov_reward::accumulated_reward(&states, &cost_rates);
+    let steady_state_cost = markov_reward::steady_state_reward_rate(&queuing_system.stationary_distribution().expect("Failed to compute stationary distribution"), &cost_rates);
+    println!("Интенсивность затрат во времени: {:?}", instantaneous_cost);
+    println!(
+        "Затраты за [0, {}]: {:?}, в установившемся режиме: {} в единицу времени",
+        queuing_system.step_size * queuing_system.num_iterations as f64, accumulated_cost.last(), steady_state_cost
+    );
+
+    // Характеристики во времени, с которыми работают операторы
+    let transient_metrics = queuing_system.transient_metrics(&states);
+    for (k, &t) in transient_metrics.times.iter().enumerate().step_by(10) {
+        println!(
+            "t = {:.2}: L = {:.4}, Lq = {:.4}, занято каналов {:.4}, P_пот = {:.4}, пропускная способность {:.4}",
+            t, transient_metrics.number_in_system[k], transient_metrics.queue_length[k], transient_metrics.busy_channels[k],
+            transient_metrics.rejection_probability[k], transient_metrics.throughput[k]
+        );
+    }
+    if export {
+        transient_metrics.export_csv("transient_metrics.csv").expect("Failed to export transient metrics");
+    }
+    if plot {
+        transient_metrics.plot(&plot_options).expect("Failed to plot transient metrics");
+    }
+
+    if plot {
+        queuing_system.plot_states(&states, &plot_options).expect("Failed to plot states");
+    }
+
+    // Время до первого заполнения системы (все каналы и места в очереди заняты)
+    let full_state = (queuing_system.num_channels + queuing_system.queue_size) as usize;
+    match queuing_system.first_passage_time(&[full_state]) {
+        Ok(passage) => println!(
+            "Время до первого заполнения системы: среднее {}, дисперсия {}",
+            passage.mean, passage.variance
+        ),
+        Err(e) => println!("Время до первого заполнения системы: {}", e),
+    }
+    let full_cdf = queuing_system.first_passage_cdf(&[full_state]);
+    println!("Функция распределения времени до заполнения: {:?}", full_cdf);
+
+    let stationary_characteristics = queuing_system.stationary_characteristics().expect("Failed to compute stationary characteristics");
+    let probabilities = stationary_characteristics.calculate_probabilities();
+    let queue_probabilities = stationary_characteristics.calculate_queue_probabilities();
+
     println!("Вероятности того, что i  каналов заняты и нет очереди: {:?}", probabilities);
     println!("Вероятности того, что все s каналов заняты и очередь длины i: {:?}", queue_probabilities);
-    println!("Вероятность отказа не попасть в очередь длины n, все каналы заняты и очередь уже сформирована: {:?}", rejection_probability);
-    println!("Среднее число заявок, поступающих за время T: {}", average_incoming_requests_during_t);
-    println!("Среднее время обслуживания заявки: {}", average_service_time_per_request);
-    println!("Среднее число занятых каналов: {}", average_busy_channels);
-    println!("Среднее число заявок в очереди: {}", average_number_of_requests_in_queue);
-    println!("Среднее время пребывания заявки в очереди: {}", average_waiting_time_in_queue);
-    println!("Общее количество заявок в системе: {}", total_number_of_requests);
-    println!("Среднее время ожидания заявки в системе: {}", average_waiting_time);
-    println!("Среднее время пребывания заявки в системе: {}", average_time_in_system);
+
+    match &estimates {
+        Some(estimates) => {
+            for interval in characteristics_report_with_intervals(estimates, config).expect("Failed to compute characteristic intervals") {
+                println!("{}: {} [{}; {}]", interval.name, interval.value, interval.lower, interval.upper);
+            }
+        }
+        None => {
+            for (name, value) in queuing_system_report(&queuing_system).expect("Failed to compute stationary characteristics") {
+                println!("{}: {}", name, value);
+            }
+        }
+    }
+
+    // Чувствительность характеристик к λ, μ, s и n: что будет при росте потока заявок на 10%?
+    let sensitivities = characteristics_sensitivity(&queuing_system).expect("Failed to compute sensitivities");
+    println!("{}", format_tornado_table(&sensitivities));
+    if let Some(rejection) = sensitivities.iter().find(|s| s.name == "Вероятность отказа") {
+        println!(
+            "При росте λ на 10% вероятность отказа изменится примерно на {:.2}%",
+            rejection.elasticity(SensitivityParameter::Lambda) * 10.0
+        );
+        if plot {
+            plot_tornado(rejection, &plot_options).expect("Failed to plot tornado chart");
+        }
+    }
+    if let Some(state_sensitivities) = queuing_system.integrate_sensitivity(SensitivityParameter::Lambda) {
+        println!("Чувствительность вероятностей состояний dp_i(t)/dλ: {:?}", state_sensitivities.last());
+        if plot {
+            queuing_system.plot_state_sensitivities(&state_sensitivities, &plot_options).expect("Failed to plot state sensitivities");
+        }
+    }
+
+    // Неопределённость λ и μ: заданные распределения и бутстреп по наблюдениям
+    let mut uncertainty_scenarios = UNCERTAINTY_CONFIG.scenarios.clone();
+    if let Some(observations) = &observations {
+        uncertainty_scenarios.insert(0, (
+            "бутстреп",
+            RateDistribution::Bootstrap(observations.interarrival_times()),
+            RateDistribution::Bootstrap(observations.service_durations.clone()),
+        ));
+    }
+    for (index, (scenario, lambda_distribution, mu_distribution)) in uncertainty_scenarios.iter().enumerate() {
+        let uncertainty = match propagate_uncertainty(
+            &queuing_system,
+            lambda_distribution,
+            mu_distribution,
+            UNCERTAINTY_CONFIG.num_samples,
+            UNCERTAINTY_CONFIG.band,
+            UNCERTAINTY_CONFIG.seed
+        ) {
+            Ok(uncertainty) => uncertainty,
+            Err(e) => {
+                println!("Монте-Карло, {}: {}", scenario, e);
+                continue;
+            }
+        };
+        println!(
+            "Монте-Карло, {} ({} выборок), квантили {:.0}%–{:.0}%:",
+            scenario, uncertainty.num_samples, UNCERTAINTY_CONFIG.band.0 * 100.0, UNCERTAINTY_CONFIG.band.1 * 100.0
+        );
+        for (name, band) in [
+            ("Вероятность отказа", uncertainty.rejection_probability),
+            ("Среднее число заявок в очереди", uncertainty.average_queue_length),
+            ("Среднее время пребывания заявки в очереди", uncertainty.average_waiting_time),
+        ] {
+            println!("  {}: медиана {:.6} [{:.6}; {:.6}]", name, band.median, band.lower, band.upper);
+        }
+        if plot && index == 0 {
+            queuing_system.plot_states_with_band(&uncertainty.transient_median, &uncertainty.transient_lower, &uncertainty.transient_upper, &plot_options)
+                .expect("Failed to plot state probability band");
+        }
+    }
+
+    if queuing_system.abandonment_rate > 0.0 {
+        println!("Вероятность ухода заявки из очереди: {}", queuing_system.calculate_abandonment_probability().expect("Failed to compute abandonment characteristics"));
+        println!("Эффективная пропускная способность: {}", queuing_system.calculate_effective_throughput().expect("Failed to compute abandonment characteristics"));
+        println!("Среднее время ожидания обслуженной заявки: {}", queuing_system.calculate_average_waiting_time_of_served().expect("Failed to compute abandonment characteristics"));
+        println!("Среднее время ожидания ушедшей заявки: {}", queuing_system.calculate_average_waiting_time_of_abandoned().expect("Failed to compute abandonment characteristics"));
+
+        match queuing_system.truncate_infinite_queue(1e-6) {
+            Ok((truncated, error_bound)) => println!(
+                "Erlang-A с неограниченной очередью: усечение до n = {} (погрешность ≤ {:e}), вероятность ухода: {}",
+                truncated.queue_size, error_bound, truncated.calculate_abandonment_probability().expect("Failed to compute abandonment characteristics")
+            ),
+            Err(e) => println!("Erlang-A с неограниченной очередью: {}", e),
+        }
+    }
+
+    if queuing_system.balking.is_some() {
+        println!("Вероятность отказа от присоединения к очереди: {}", queuing_system.calculate_balking_probability().expect("Failed to compute balking characteristics"));
+        println!("Вероятность отказа из-за заполненной очереди: {}", queuing_system.calculate_full_buffer_rejection_probability().expect("Failed to compute balking characteristics"));
+        println!("Интенсивность потока вошедших заявок: {}", queuing_system.calculate_effective_arrival_rate().expect("Failed to compute balking characteristics"));
+    }
+
+    let finite_source_system = FiniteSourceQueuingSystem::new(
+        FINITE_SOURCE_CONFIG.lambda_rate,
+        FINITE_SOURCE_CONFIG.mu_rate,
+        FINITE_SOURCE_CONFIG.num_channels,
+        FINITE_SOURCE_CONFIG.num_sources,
+        FINITE_SOURCE_CONFIG.queue_size,
+        FINITE_SOURCE_CONFIG.time
+    );
+
+    println!("Модель ремонта станков (N = {}):", finite_source_system.num_sources);
+    println!("Правые части уравнений Колмогорова: {:?}", finite_source_system.generate_kolmogorov_matrix());
+    println!("Вероятности состояний: {:?}", finite_source_system.calculate_probabilities());
+    println!("Вероятности очереди на ремонт: {:?}", finite_source_system.calculate_queue_probabilities());
+    for (name, value) in characteristics_report(&finite_source_system) {
+        println!("{}: {}", name, value);
+    }
+    println!("Коэффициент использования ремонтной бригады: {}", finite_source_system.calculate_crew_utilisation());
+    println!("Коэффициент готовности станков: {}", finite_source_system.calculate_machine_availability());
+
+    for policy in [AssignmentPolicy::FastestFreeFirst, AssignmentPolicy::Random, AssignmentPolicy::RoundRobin] {
+        let heterogeneous_system = HeterogeneousQueuingSystem::new(
+            HETEROGENEOUS_CONFIG.lambda_rate,
+            HETEROGENEOUS_CONFIG.mu_rates.clone(),
+            HETEROGENEOUS_CONFIG.queue_size,
+            policy,
+            HETEROGENEOUS_CONFIG.time
+        ).expect("Invalid heterogeneous channel configuration");
+
+        println!("Разнородные каналы μ = {:?}, правило назначения {:?}:", heterogeneous_system.mu_rates, policy);
+        println!("Загрузка каналов: {:?}", heterogeneous_system.calculate_channel_utilisations().expect("Failed to compute channel utilisations"));
+        for (name, value) in characteristics_report(&heterogeneous_system.characteristics().expect("Failed to compute heterogeneous characteristics")) {
+            println!("{}: {}", name, value);
+        }
+    }
+
+    let priority_classes: Vec<PriorityClass> = PRIORITY_CONFIG.lambda_rates.iter()
+        .zip(PRIORITY_CONFIG.mu_rates.iter())
+        .map(|(&lambda_rate, &mu_rate)| PriorityClass { lambda_rate, mu_rate })
+        .collect();
+    for discipline in [PriorityDiscipline::PreemptiveResume, PriorityDiscipline::NonPreemptive] {
+        let priority_system = PriorityQueuingSystem::new(
+            priority_classes.clone(),
+            PRIORITY_CONFIG.num_channels,
+            PRIORITY_CONFIG.queue_size,
+            discipline
+        );
+
+        println!("Классы с приоритетами, дисциплина {:?}:", discipline);
+        let analytic = priority_system.calculate_class_characteristics().expect("Failed to compute class characteristics");
+        let simulated = priority_system.simulate(20_000.0, 100.0, 42);
+        for (class, (analytic, simulated)) in analytic.iter().zip(simulated.iter()).enumerate() {
+            for (method, values) in [("цепь Маркова", analytic), ("имитация", simulated)] {
+                println!(
+                    "  Класс {} ({}): P_отк = {:.4}, Lq = {:.4}, L = {:.4}, Wq = {:.4}, W = {:.4}",
+                    class, method, values.rejection_probability, values.average_number_in_queue,
+                    values.average_number_in_system, values.average_waiting_time_in_queue, values.average_time_in_system
+                );
+            }
+        }
+    }
+
+    for policy in [BatchRejectionPolicy::Partial, BatchRejectionPolicy::WholeBatch] {
+        // Интенсивность потока групп подбирается так, чтобы поток заявок оставался равным λ
+        let batch_arrivals = BatchArrivals::new(BATCH_SIZE_PROBABILITIES.clone(), policy)
+            .expect("Invalid batch size distribution");
+        let batch_system = QueuingSystem {
+            lambda_rate: queuing_system.lambda_rate / batch_arrivals.mean_size(),
+            batch_arrivals: Some(batch_arrivals),
+            ..queuing_system.clone()
+        };
+
+        println!("Групповые поступления {:?}, правило приёма {:?}:", BATCH_SIZE_PROBABILITIES.as_slice(), policy);
+        println!("Правые части уравнений Колмогорова: {:?}", batch_system.generate_kolmogorov_matrix());
+        for (name, value) in characteristics_report(&batch_system.stationary_characteristics().expect("Failed to compute batch characteristics")) {
+            println!("{}: {}", name, value);
+        }
+    }
+
+    let unreliable_system = UnreliableQueuingSystem::new(
+        config.lambda_rate,
+        config.mu_rate,
+        RELIABILITY_CONFIG.failure_rate,
+        RELIABILITY_CONFIG.repair_rate,
+        config.num_channels,
+        config.queue_size,
+        config.time,
+        config.num_iterations,
+        config.step_size
+    );
+
+    println!("Система с отказами каналов (ξ = {}, η = {}):", unreliable_system.failure_rate, unreliable_system.repair_rate);
+    let unreliable_states = unreliable_system.integrate_system();
+    if let Some((_, last)) = unreliable_states.last() {
+        println!("Вероятности числа заявок в момент T: {:?}", unreliable_system.customer_distribution(last).as_slice());
+    }
+    // Время до первого отказа всех каналов в произвольном графе состояний системы с отказами
+    let unreliable_matrix = unreliable_system.generate_kolmogorov_matrix();
+    let unreliable_states_list = unreliable_system.states();
+    let all_failed: Vec<usize> = unreliable_states_list.iter()
+        .enumerate()
+        .filter(|(_, state)| state.working == 0)
+        .map(|(i, _)| i)
+        .collect();
+    let all_working_empty = unreliable_states_list.iter()
+        .position(|state| state.customers == 0 && state.working == unreliable_system.num_channels)
+        .expect("empty system with all channels working");
+    let mut unreliable_initial = DVector::zeros(unreliable_states_list.len());
+    unreliable_initial[all_working_empty] = 1.0;
+    match markov_chain::first_passage_time(&unreliable_matrix, &unreliable_initial, &all_failed) {
+        Ok(passage) => println!(
+            "Время до отказа всех каналов: среднее {}, дисперсия {}, P(τ ≤ T) = {:?}",
+            passage.mean, passage.variance,
+            markov_chain::first_passage_cdf(&unreliable_matrix, unreliable_initial, &all_failed, config.step_size, config.num_iterations).last().map(|&(_, probability)| probability)
+        ),
+        Err(e) => println!("Время до отказа всех каналов: {}", e),
+    }
+    if plot {
+        unreliable_system.state_graph().plot("unreliable_system_state_graph", &plot_options).expect("Failed to render state graph");
+    }
+    if export {
+        unreliable_system.state_graph().export_dot("unreliable_system_states.dot").expect("Failed to export state graph");
+    }
+    println!("Коэффициент готовности каналов: {}", unreliable_system.calculate_availability().expect("Failed to compute reliability characteristics"));
+    println!("Эффективная производительность: {}", unreliable_system.calculate_effective_capacity().expect("Failed to compute reliability characteristics"));
+    println!("Вероятность отказа при неисправных каналах: {}", unreliable_system.calculate_degraded_rejection_probability().expect("Failed to compute reliability characteristics"));
+    for (name, value) in characteristics_report(&unreliable_system.characteristics().expect("Failed to compute reliability characteristics")) {
+        println!("{}: {}", name, value);
+    }
+
+    let retrial_system = RetrialQueuingSystem::new(
+        config.lambda_rate,
+        config.mu_rate,
+        RETRIAL_CONFIG.retrial_rate,
+        RETRIAL_CONFIG.persistence,
+        config.num_channels,
+        config.queue_size,
+        RETRIAL_CONFIG.orbit_size,
+        config.time
+    );
+
+    println!(
+        "Повторные вызовы (θ = {}, настойчивость p = {}, орбита до {} заявок):",
+        retrial_system.retrial_rate, retrial_system.persistence, retrial_system.orbit_size
+    );
+    println!("Среднее число заявок на орбите: {}", retrial_system.calculate_average_orbit_size().expect("Failed to compute retrial characteristics"));
+    println!("Интенсивность первичных и повторных вызовов: {}", retrial_system.calculate_attempt_rate().expect("Failed to compute retrial characteristics"));
+    println!("Эффективная интенсивность потока заявок: {}", retrial_system.calculate_effective_arrival_rate().expect("Failed to compute retrial characteristics"));
+    println!("Вероятность окончательной потери заявки: {}", retrial_system.calculate_eventual_loss_probability().expect("Failed to compute retrial characteristics"));
+    println!("Вероятность достижения границы орбиты: {:e}", retrial_system.calculate_truncation_probability().expect("Failed to compute retrial characteristics"));
+    for (name, value) in characteristics_report(&retrial_system.characteristics().expect("Failed to compute retrial characteristics")) {
+        println!("{}: {}", name, value);
+    }
+
+    // Сравнение M/M/1 (очередь достаточно длинная, чтобы не влиять на результат) с M/D/1 и M/G/1
+    let single_channel_queue_size = 100;
+    let mm1_system = QueuingSystem::new(
+        SINGLE_CHANNEL_CONFIG.lambda_rate,
+        SINGLE_CHANNEL_CONFIG.mu_rate,
+        0.0,
+        None,
+        None,
+        1,
+        single_channel_queue_size,
+        Config::empty_initial_state(1, single_channel_queue_size),
+        SINGLE_CHANNEL_CONFIG.time,
+        config.num_iterations,
+        config.step_size
+    );
+    let md1_system = MG1QueuingSystem::deterministic(SINGLE_CHANNEL_CONFIG.lambda_rate, SINGLE_CHANNEL_CONFIG.mu_rate, SINGLE_CHANNEL_CONFIG.time);
+    let mut comparison = vec![
+        ("M/M/1".to_string(), queuing_system_report(&mm1_system).expect("Failed to compute M/M/1 characteristics")),
+        ("M/D/1".to_string(), characteristics_report(&md1_system)),
+    ];
+    for &service_cv in &SINGLE_CHANNEL_CONFIG.service_cvs {
+        let mg1_system = MG1QueuingSystem::new(SINGLE_CHANNEL_CONFIG.lambda_rate, SINGLE_CHANNEL_CONFIG.mu_rate, service_cv, SINGLE_CHANNEL_CONFIG.time);
+        comparison.push((format!("M/G/1 c={}", service_cv), characteristics_report(&mg1_system)));
+    }
+    let comparison: Vec<(&str, Vec<(&'static str, f64)>)> = comparison.iter()
+        .map(|(model, report)| (model.as_str(), report.clone()))
+        .collect();
+
+    println!("{}", format_comparison_table(&comparison));
+    if export {
+        export_comparison_csv("single_channel_comparison.csv", &comparison).expect("Failed to export comparison");
+    }
+
+    // Приближения G/G/s: коэффициенты вариации берутся из наблюдений, если они заданы
+    let (arrival_scv, service_scv) = estimates.as_ref()
+        .map_or((GGS_CONFIG.arrival_scv, GGS_CONFIG.service_scv), |estimates| (estimates.lambda.squared_cv, estimates.mu.squared_cv));
+    let mms_system = GGSQueuingSystem::markovian(config.lambda_rate, config.mu_rate, GGS_CONFIG.num_channels, GGS_CONFIG.time);
+    println!(
+        "G/G/{} (c_a² = {:.4}, c_s² = {:.4}), вероятность ожидания M/M/s по формуле Эрланга C: {}",
+        GGS_CONFIG.num_channels, arrival_scv, service_scv, mms_system.calculate_erlang_c()
+    );
+    let mut ggs_comparison = vec![(format!("M/M/{}", GGS_CONFIG.num_channels), characteristics_report(&mms_system))];
+    for approximation in [GGSApproximation::Kingman, GGSApproximation::AllenCunneen] {
+        let ggs_system = GGSQueuingSystem::new(
+            config.lambda_rate,
+            config.mu_rate,
+            GGS_CONFIG.num_channels,
+            arrival_scv,
+            service_scv,
+            approximation,
+            GGS_CONFIG.time
+        );
+        println!(
+            "{:?}: вероятность ожидания {}, Wq / Wq(M/M/s) = {}",
+            approximation, ggs_system.calculate_waiting_probability(),
+            ggs_system.calculate_average_waiting_time_in_queue() / mms_system.calculate_average_waiting_time_in_queue()
+        );
+        let column = match approximation {
+            GGSApproximation::Kingman => "Kingman",
+            GGSApproximation::AllenCunneen => "Allen-Cunneen",
+        };
+        ggs_comparison.push((column.to_string(), characteristics_report(&ggs_system)));
+    }
+    let ggs_comparison: Vec<(&str, Vec<(&'static str, f64)>)> = ggs_comparison.iter()
+        .map(|(model, report)| (model.as_str(), report.clone()))
+        .collect();
+
+    println!("{}", format_comparison_table(&ggs_comparison));
+    if export {
+        export_comparison_csv("ggs_comparison.csv", &ggs_comparison).expect("Failed to export comparison");
+    }
+
+    // Фазовое обслуживание: состояния раскрываются по фазам, результаты сводятся к числу заявок
+    let phase_type_distributions = [
+        ("Exponential", PhaseTypeDistribution::erlang(1, config.mu_rate)),
+        ("Erlang", PhaseTypeDistribution::erlang(PHASE_TYPE_CONFIG.erlang_phases, config.mu_rate)),
+        ("Hyperexp", PhaseTypeDistribution::hyperexponential(
+            PHASE_TYPE_CONFIG.hyperexponential_probabilities.clone(),
+            PHASE_TYPE_CONFIG.hyperexponential_rates.clone()
+        )),
+        ("Coxian", PhaseTypeDistribution::coxian(
+            PHASE_TYPE_CONFIG.coxian_rates.clone(),
+            PHASE_TYPE_CONFIG.coxian_continue_probabilities.clone()
+        )),
+    ];
+    let mut phase_type_comparison = Vec::new();
+    for (name, service) in phase_type_distributions {
+        let service = service.expect("Invalid phase-type distribution");
+        let phase_type_system = PhaseTypeQueuingSystem::new(
+            config.lambda_rate,
+            service,
+            config.num_channels,
+            config.queue_size,
+            config.time,
+            config.num_iterations,
+            config.step_size
+        );
+
+        println!(
+            "Фазовое обслуживание {} (фаз: {}): E[X] = {}, c_s² = {}, состояний: {}",
+            name, phase_type_system.service.phases(), phase_type_system.service.mean().expect("Failed to compute service moments"),
+            phase_type_system.service.squared_cv().expect("Failed to compute service moments"), phase_type_system.states().len()
+        );
+        if let Some((_, last)) = phase_type_system.integrate_system().last() {
+            println!("Вероятности числа заявок в момент T: {:?}", last.as_slice());
+        }
+        phase_type_comparison.push((name, characteristics_report(&phase_type_system.characteristics().expect("Failed to compute phase-type characteristics"))));
+    }
+
+    println!("{}", format_comparison_table(&phase_type_comparison));
+    if export {
+        export_comparison_csv("phase_type_comparison.csv", &phase_type_comparison).expect("Failed to export comparison");
+    }
+
+    // Открытая сеть станций: с неограниченными очередями — мультипликативная форма и имитация,
+    // с ограниченными очередями — только имитация
+    let network_for = |finite_buffers: bool| JacksonNetwork::new(
+        (0..NETWORK_CONFIG.station_names.len()).map(|i| NetworkStation {
+            name: NETWORK_CONFIG.station_names[i].to_string(),
+            num_channels: NETWORK_CONFIG.num_channels[i],
+            mu_rate: NETWORK_CONFIG.mu_rates[i],
+            queue_size: if finite_buffers { Some(NETWORK_CONFIG.queue_sizes[i]) } else { None },
+            external_arrival_rate: NETWORK_CONFIG.external_arrival_rates[i],
+        }).collect(),
+        NETWORK_CONFIG.routing.clone()
+    ).expect("Invalid network configuration");
+    let infinite_network = network_for(false);
+    let finite_network = network_for(true);
+
+    match infinite_network.solve_traffic_equations() {
+        Ok(arrival_rates) => println!("Сеть станций, решение уравнений трафика: {:?}", arrival_rates),
+        Err(e) => println!("Сеть станций: {}", e),
+    }
+    let network_results = [
+        ("мультипликативная форма", &infinite_network, infinite_network.product_form_metrics()),
+        ("имитация", &infinite_network, Ok(infinite_network.simulate(20_000.0, 100.0, 42))),
+        ("имитация, ограниченные очереди", &finite_network, Ok(finite_network.simulate(20_000.0, 100.0, 42))),
+    ];
+    for (method, network, result) in network_results {
+        match result {
+            Ok(metrics) => {
+                println!(
+                    "Сеть станций ({}): пропускная способность = {:.4}, вероятность потери = {:.4}, время пребывания = {:.4}, узкое место: {}",
+                    method, metrics.throughput, metrics.loss_probability, metrics.average_sojourn_time,
+                    network.stations[metrics.bottleneck].name
+                );
+                for (station, values) in network.stations.iter().zip(metrics.stations.iter()) {
+                    println!(
+                        "  {}: λ = {:.4}, ρ = {:.4}, P_пот = {:.4}, Lq = {:.4}, L = {:.4}, W = {:.4}",
+                        station.name, values.arrival_rate, values.utilisation, values.rejection_probability,
+                        values.average_number_in_queue, values.average_number_in_system, values.average_time_in_system
+                    );
+                }
+            }
+            Err(e) => println!("Сеть станций ({}): {}", method, e),
+        }
+    }
+
+    // Замкнутая сеть: точный анализ средних значений и приближение Швейцера
+    let closed_network = ClosedNetwork::new(
+        (0..CLOSED_NETWORK_CONFIG.station_names.len()).map(|i| ClosedStation {
+            name: CLOSED_NETWORK_CONFIG.station_names[i].to_string(),
+            visit_ratio: CLOSED_NETWORK_CONFIG.visit_ratios[i],
+            service_time: CLOSED_NETWORK_CONFIG.service_times[i],
+        }).collect()
+    );
+    let exact_mva = closed_network.exact_mva(CLOSED_NETWORK_CONFIG.max_population);
+    let schweitzer_mva: Vec<_> = (1..=CLOSED_NETWORK_CONFIG.max_population)
+        .map(|population| closed_network.schweitzer_mva(population, 1e-9).expect("Schweitzer MVA did not converge"))
+        .collect();
+
+    let station_names: Vec<&str> = closed_network.stations.iter().map(|station| station.name.as_str()).collect();
+    println!("Замкнутая сеть {:?}:", station_names);
+    for (exact, approximate) in exact_mva.iter().zip(schweitzer_mva.iter()) {
+        println!(
+            "  N = {}: X = {:.4} (Швейцер {:.4}), R = {:.4} (Швейцер {:.4}), R_k = {:.4?}, Q_k = {:.4?}, U_k = {:.4?}",
+            exact.population, exact.throughput, approximate.throughput, exact.response_time, approximate.response_time,
+            exact.station_response_times, exact.station_queue_lengths, exact.utilisations
+        );
+    }
+
+    if plot {
+        closed_network.plot_throughput(&[("Exact MVA", exact_mva), ("Schweitzer", schweitzer_mva)], &plot_options)
+            .expect("Failed to plot closed network throughput");
+    }
 }
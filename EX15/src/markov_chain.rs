@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::hash::Hash;
+
+use nalgebra::{DMatrix, DVector};
+
+/// Моменты времени первого достижения множества состояний.
+#[derive(Debug, Clone, Copy)]
+pub struct FirstPassageTime {
+    pub mean: f64,     // Математическое ожидание
+    pub variance: f64, // Дисперсия
+}
+
+// Функция для преобразования Vec<Vec<f64>> в DMatrix<f64>
+pub fn kolmogorov_matrix_to_dmatrix(matrix: Vec<Vec<f64>>) -> DMatrix<f64> {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, Vec::len);
+
+    let flat_matrix: Vec<f64> = matrix.into_iter()
+        .flatten()
+        .collect();
+
+    DMatrix::from_row_slice(rows, cols, &flat_matrix)
+}
+
+/// Перечисляет состояния цепи, достижимые из начального, обходом в ширину.
+/// # Параметры
+/// * `initial` - Начальное состояние, получает номер 0.
+/// * `transitions` - Переходы из состояния: пары (новое состояние, интенсивность).
+/// # Возвращаемое значение
+/// Состояния в порядке обнаружения, тип: `Vec<S>`.
+pub fn reachable_states<S, F>(initial: S, transitions: F) -> Vec<S>
+where
+    S: Clone + Eq + Hash,
+    F: Fn(&S) -> Vec<(S, f64)>,
+{
+    let mut states = vec![initial.clone()];
+    let mut known: HashMap<S, usize> = HashMap::from([(initial, 0)]);
+    let mut next_to_visit = 0;
+    while next_to_visit < states.len() {
+        for (next, _) in transitions(&states[next_to_visit]) {
+            if !known.contains_key(&next) {
+                known.insert(next.clone(), states.len());
+                states.push(next);
+            }
+        }
+        next_to_visit += 1;
+    }
+
+    states
+}
+
+/// Строит матрицу правых частей уравнений Колмогорова по переходам из каждого состояния:
+/// A[to][from] накапливает интенсивности переходов, диагональ — интенсивность выхода со знаком минус.
+/// # Параметры
+/// * `states` - Состояния в порядке нумерации строк матрицы.
+/// * `transitions` - Переходы из состояния: пары (новое состояние, интенсивность).
+///   Новое состояние должно входить в `states`.
+/// # Возвращаемое значение
+/// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+pub fn generator_matrix<S, F>(states: &[S], transitions: F) -> DMatrix<f64>
+where
+    S: Eq + Hash,
+    F: Fn(&S) -> Vec<(S, f64)>,
+{
+    let index: HashMap<&S, usize> = states.iter()
+        .enumerate()
+        .map(|(i, state)| (state, i))
+        .collect();
+
+    let mut matrix = DMatrix::zeros(states.len(), states.len());
+    for (from, state) in states.iter().enumerate() {
+        for (next, rate) in transitions(state) {
+            let to = index[&next];
+            matrix[(to, from)] += rate;
+            matrix[(from, from)] -= rate;
+        }
+    }
+
+    matrix
+}
+
+/// Сводит распределение по состояниям цепи к распределению признака состояния,
+/// например числа заявок в системе.
+/// # Параметры
+/// * `states` - Состояния в порядке нумерации вероятностей.
+/// * `distribution` - Вероятности состояний.
+/// * `size` - Число значений признака 0..size.
+/// * `key` - Значение признака для состояния.
+/// # Возвращаемое значение
+/// Вероятности значений признака, тип: `DVector<f64>`.
+pub fn marginal_distribution<S, F>(states: &[S], distribution: &DVector<f64>, size: usize, key: F) -> DVector<f64>
+where
+    F: Fn(&S) -> usize,
+{
+    let mut aggregated = DVector::zeros(size);
+    for (state, p) in states.iter().zip(distribution.iter()) {
+        aggregated[key(state)] += p;
+    }
+    aggregated
+}
+
+// Функция f(t, x), возвращающая производную состояния
+fn f(_t: f64, state: &DVector<f64>, matrix: &DMatrix<f64>) -> DVector<f64> {
+    matrix * state
+}
+
+// Метод Рунге-Кутты 4-го порядка для одного шага
+fn runge_kutta4_step(state: &DVector<f64>, matrix: &DMatrix<f64>, t: f64, dt: f64) -> DVector<f64> {
+    let k1 = f(t, state, matrix);
+    let k2 = f(t + dt / 2.0, &(state + &k1 * (dt / 2.0)), matrix);
+    let k3 = f(t + dt / 2.0, &(state + &k2 * (dt / 2.0)), matrix);
+    let k4 = f(t + dt, &(state + &k3 * dt), matrix);
+
+    state + &k1 * (dt / 6.0) + &k2 * (dt / 3.0) + &k3 * (dt / 3.0) + &k4 * (dt / 6.0)
+}
+
+/// Интегрирует систему уравнений Колмогорова dp/dt = A·p методом Рунге-Кутты 4-го порядка.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `initial_state` - Начальное распределение вероятностей состояний.
+/// * `step_size` - Шаг интегрирования.
+/// * `num_iterations` - Количество шагов.
+/// # Возвращаемое значение
+/// Пары (t, p(t)) в моменты 0, h, 2h, ..., тип: `Vec<(f64, DVector<f64>)>`.
+pub fn integrate(matrix: &DMatrix<f64>, initial_state: DVector<f64>, step_size: f64, num_iterations: i32) -> Vec<(f64, DVector<f64>)> {
+    std::iter::successors(Some((initial_state, 0.0)), |(last_state, t)| {
+        // Нормализация нового состояния
+        let new_state = runge_kutta4_step(last_state, matrix, *t, step_size);
+        let sum: f64 = new_state.iter().sum();
+        Some((new_state / sum, t + step_size))
+    })
+        .take((num_iterations + 1) as usize)
+        .map(|(state, t)| (t, state))
+        .collect()
+}
+
+/// Интегрирует уравнения чувствительности ds/dt = A·s + (∂A/∂θ)·p совместно с dp/dt = A·p,
+/// где s = ∂p/∂θ. Начальное распределение не зависит от θ, поэтому s(0) = 0.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `derivative_matrix` - Производная матрицы по параметру θ.
+/// * `initial_state` - Начальное распределение вероятностей состояний.
+/// * `step_size` - Шаг интегрирования.
+/// * `num_iterations` - Количество шагов.
+/// # Возвращаемое значение
+/// Пары (t, ∂p(t)/∂θ) в моменты 0, h, 2h, ..., тип: `Vec<(f64, DVector<f64>)>`.
+pub fn integrate_sensitivity(matrix: &DMatrix<f64>, derivative_matrix: &DMatrix<f64>, initial_state: DVector<f64>, step_size: f64, num_iterations: i32) -> Vec<(f64, DVector<f64>)> {
+    let size = matrix.nrows();
+    let mut augmented = DMatrix::zeros(2 * size, 2 * size);
+    augmented.view_mut((0, 0), (size, size)).copy_from(matrix);
+    augmented.view_mut((size, 0), (size, size)).copy_from(derivative_matrix);
+    augmented.view_mut((size, size), (size, size)).copy_from(matrix);
+
+    let mut augmented_state = DVector::zeros(2 * size);
+    augmented_state.rows_mut(0, size).copy_from(&initial_state);
+
+    std::iter::successors(Some((augmented_state, 0.0)), |(last_state, t)| {
+        Some((runge_kutta4_step(last_state, &augmented, *t, step_size), t + step_size))
+    })
+        .take((num_iterations + 1) as usize)
+        .map(|(state, t)| (t, state.rows(size, size).into_owned()))
+        .collect()
+}
+
+/// Находит стационарное распределение из условия A·p = 0, Σp = 1.
+/// Одно из уравнений системы заменяется условием нормировки.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// # Возвращаемое значение
+/// Стационарные вероятности состояний либо ошибка, если цепь не неприводима,
+/// тип: `Result<DVector<f64>, Box<dyn Error>>`.
+pub fn stationary_distribution(matrix: &DMatrix<f64>) -> Result<DVector<f64>, Box<dyn Error>> {
+    let number_of_states = matrix.nrows();
+    let mut system = matrix.clone();
+    system.row_mut(number_of_states - 1).fill(1.0);
+
+    let mut right_hand_side = DVector::zeros(number_of_states);
+    right_hand_side[number_of_states - 1] = 1.0;
+
+    let solution = system.lu().solve(&right_hand_side)
+        .ok_or("стационарное распределение не единственно: цепь не неприводима")?;
+
+    // Отсекаем отрицательные значения, возникающие из-за погрешностей округления
+    let solution = solution.map(|p| p.max(0.0));
+    let sum = solution.sum();
+    Ok(solution / sum)
+}
+
+/// Находит производную стационарного распределения по параметру θ.
+/// Дифференцируя A·p = 0, Σp = 1, получаем A·∂p = -(∂A/∂θ)·p, Σ∂p = 0;
+/// как и в `stationary_distribution`, одно из уравнений заменяется условием нормировки.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `derivative_matrix` - Производная матрицы по параметру θ.
+/// * `distribution` - Стационарное распределение цепи.
+/// # Возвращаемое значение
+/// Производные стационарных вероятностей ∂p/∂θ либо ошибка, если цепь не неприводима,
+/// тип: `Result<DVector<f64>, Box<dyn Error>>`.
+pub fn stationary_sensitivity(matrix: &DMatrix<f64>, derivative_matrix: &DMatrix<f64>, distribution: &DVector<f64>) -> Result<DVector<f64>, Box<dyn Error>> {
+    let number_of_states = matrix.nrows();
+    let mut system = matrix.clone();
+    system.row_mut(number_of_states - 1).fill(1.0);
+
+    let mut right_hand_side = -(derivative_matrix * distribution);
+    right_hand_side[number_of_states - 1] = 0.0;
+
+    Ok(system.lu().solve(&right_hand_side)
+        .ok_or("стационарное распределение не единственно: цепь не неприводима")?)
+}
+
+/// Делает состояния `targets` поглощающими: из них больше нет переходов.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `targets` - Номера поглощающих состояний.
+/// # Возвращаемое значение
+/// Матрица цепи с поглощением, тип: `DMatrix<f64>`.
+pub fn make_absorbing(matrix: &DMatrix<f64>, targets: &[usize]) -> DMatrix<f64> {
+    let mut absorbing = matrix.clone();
+    for &target in targets {
+        absorbing.column_mut(target).fill(0.0);
+    }
+    absorbing
+}
+
+/// Вычисляет среднее и дисперсию времени первого достижения состояний `targets`.
+/// Для непоглощающих состояний m = (-Q)⁻¹·1 и m₂ = 2·(-Q)⁻¹·m,
+/// где Q — подматрица интенсивностей переходов между ними.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `initial_state` - Начальное распределение вероятностей состояний.
+/// * `targets` - Номера целевых состояний.
+/// # Возвращаемое значение
+/// Моменты времени достижения, тип: `Result<FirstPassageTime, Box<dyn Error>>`.
+pub fn first_passage_time(matrix: &DMatrix<f64>, initial_state: &DVector<f64>, targets: &[usize]) -> Result<FirstPassageTime, Box<dyn Error>> {
+    let transient: Vec<usize> = (0..matrix.nrows()).filter(|i| !targets.contains(i)).collect();
+    if transient.is_empty() {
+        return Ok(FirstPassageTime { mean: 0.0, variance: 0.0 });
+    }
+
+    // Матрица A хранит переходы по столбцам (A[to][from]), поэтому Q — транспонированная подматрица
+    let minus_q = DMatrix::from_fn(transient.len(), transient.len(), |row, col| -matrix[(transient[col], transient[row])]);
+    let lu = minus_q.lu();
+    let mean_times = lu.solve(&DVector::from_element(transient.len(), 1.0))
+        .ok_or("целевые состояния недостижимы из некоторых состояний")?;
+    let second_moments = lu.solve(&mean_times)
+        .ok_or("целевые состояния недостижимы из некоторых состояний")? * 2.0;
+
+    let initial = DVector::from_iterator(transient.len(), transient.iter().map(|&i| initial_state[i]));
+    let mean = initial.dot(&mean_times);
+    Ok(FirstPassageTime {
+        mean,
+        variance: initial.dot(&second_moments) - mean.powi(2),
+    })
+}
+
+/// Вычисляет функцию распределения времени первого достижения состояний `targets`
+/// интегрированием уравнений Колмогорова цепи с поглощением.
+/// # Параметры
+/// * `matrix` - Матрица правых частей уравнений Колмогорова.
+/// * `initial_state` - Начальное распределение вероятностей состояний.
+/// * `targets` - Номера целевых состояний.
+/// * `step_size` - Шаг интегрирования.
+/// * `num_iterations` - Количество шагов.
+/// # Возвращаемое значение
+/// Пары (t, P(τ ≤ t)) в моменты 0, h, 2h, ..., тип: `Vec<(f64, f64)>`.
+pub fn first_passage_cdf(matrix: &DMatrix<f64>, initial_state: DVector<f64>, targets: &[usize], step_size: f64, num_iterations: i32) -> Vec<(f64, f64)> {
+    integrate(&make_absorbing(matrix, targets), initial_state, step_size, num_iterations)
+        .iter()
+        .map(|(t, state)| (*t, targets.iter().map(|&target| state[target]).sum()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stationary_distribution_of_two_state_chain() {
+        // S_0 → S_1 с интенсивностью 2, S_1 → S_0 с интенсивностью 3: p = (3/5, 2/5)
+        let matrix = DMatrix::from_row_slice(2, 2, &[-2.0, 3.0, 2.0, -3.0]);
+        let distribution = stationary_distribution(&matrix).unwrap();
+
+        assert!((distribution[0] - 0.6).abs() < 1e-12);
+        assert!((distribution[1] - 0.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reducible_chain_has_no_unique_stationary_distribution() {
+        // Оба состояния поглощающие: любое распределение стационарно
+        let matrix = DMatrix::zeros(2, 2);
+
+        assert!(stationary_distribution(&matrix).is_err());
+        assert!(stationary_sensitivity(&matrix, &matrix, &DVector::from_element(2, 0.5)).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use nalgebra::DVector;
+
+/// Вычисляет ожидаемое мгновенное вознаграждение r·p(t) вдоль траектории уравнений Колмогорова.
+/// # Параметры
+/// * `states` - Пары (t, p(t)) траектории уравнений Колмогорова.
+/// * `rewards` - Интенсивность вознаграждения (или затрат) в каждом состоянии.
+/// # Возвращаемое значение
+/// Ожидаемая интенсивность вознаграждения в каждый момент траектории, тип: `Vec<f64>`.
+pub fn instantaneous_reward(states: &[(f64, DVector<f64>)], rewards: &DVector<f64>) -> Vec<f64> {
+    states.iter().map(|(_, state)| rewards.dot(state)).collect()
+}
+
+/// Вычисляет ожидаемое вознаграждение, накопленное на [0, t], интегрированием
+/// мгновенного вознаграждения по формуле трапеций.
+/// # Параметры
+/// * `states` - Пары (t, p(t)) траектории уравнений Колмогорова.
+/// * `rewards` - Интенсивность вознаграждения (или затрат) в каждом состоянии.
+/// # Возвращаемое значение
+/// Накопленное вознаграждение в каждый момент траектории, тип: `Vec<f64>`.
+pub fn accumulated_reward(states: &[(f64, DVector<f64>)], rewards: &DVector<f64>) -> Vec<f64> {
+    let instantaneous = instantaneous_reward(states, rewards);
+    let mut total = 0.0;
+    std::iter::once(0.0)
+        .chain(states.windows(2).zip(instantaneous.windows(2)).map(|(points, pair)| {
+            total += (pair[0] + pair[1]) / 2.0 * (points[1].0 - points[0].0);
+            total
+        }))
+        .take(instantaneous.len())
+        .collect()
+}
+
+/// Вычисляет стационарную интенсивность вознаграждения Σ r_i·p_i.
+/// # Параметры
+/// * `distribution` - Стационарное распределение вероятностей состояний.
+/// * `rewards` - Интенсивность вознаграждения (или затрат) в каждом состоянии.
+/// # Возвращаемое значение
+/// Средняя интенсивность вознаграждения в установившемся режиме, тип: `f64`.
+pub fn steady_state_reward_rate(distribution: &DVector<f64>, rewards: &DVector<f64>) -> f64 {
+    rewards.dot(distribution)
+}
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+/// Одноканальная СМО M/G/1 с неограниченной очередью.
+/// Средние характеристики вычисляются по формуле Поллачека–Хинчина и зависят
+/// только от среднего времени обслуживания 1/μ и его коэффициента вариации.
+pub struct MG1QueuingSystem {
+    pub lambda_rate: f64, // Интенсивность потока заявок
+    pub mu_rate: f64,     // Интенсивность обслуживания (величина, обратная среднему времени обслуживания)
+    pub service_cv: f64,  // Коэффициент вариации времени обслуживания
+    pub time: i32,        // Время
+}
+
+impl MG1QueuingSystem {
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               service_cv: f64,
+               time: i32
+    ) -> MG1QueuingSystem {
+
+        MG1QueuingSystem {
+            lambda_rate,
+            mu_rate,
+            service_cv,
+            time
+        }
+    }
+
+    /// Создаёт систему M/D/1 с детерминированным временем обслуживания.
+    pub fn deterministic(lambda_rate: f64, mu_rate: f64, time: i32) -> MG1QueuingSystem {
+        MG1QueuingSystem::new(lambda_rate, mu_rate, 0.0, time)
+    }
+}
+
+impl QueuingSystemCharacteristics for MG1QueuingSystem {
+    /// 1
+    /// Вычисляет коэффициент загрузки ρ = λ / μ.
+    /// # Возвращаемое значение
+    /// Коэффициент загрузки системы СМО, тип: `f64`.
+    fn calculate_load_factor(&self) -> f64 {
+        self.lambda_rate / self.mu_rate
+    }
+
+    /// 2
+    /// Вычисляет вероятность простоя системы P0 = 1 - ρ.
+    /// # Возвращаемое значение
+    /// Вероятность простоя системы, тип: `f64`.
+    fn calculate_probability_of_downtime(&self) -> f64 {
+        (1.0 - self.calculate_load_factor()).max(0.0)
+    }
+
+    /// Вычисляет факториал числа.
+    /// # Параметры
+    /// * `n` - Число, для которого вычисляется факториал.
+    /// # Возвращаемое значение
+    /// Факториал заданного числа, тип: `u64`.
+    fn factorial(n: u64) -> u64 {
+        (1..=n).product()
+    }
+
+    /// 3
+    /// Вероятность простоя канала. Вероятности остальных состояний M/G/1
+    /// не определяются средним и коэффициентом вариации времени обслуживания.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_probabilities(&self) -> BTreeMap<String, f64> {
+        BTreeMap::from([("P_0".to_string(), self.calculate_probability_of_downtime())])
+    }
+
+    /// 4
+    /// Распределение длины очереди M/G/1 не определяется средним и коэффициентом вариации.
+    /// # Возвращаемое значение
+    /// Пустая таблица, тип: `BTreeMap<String, f64>`.
+    fn calculate_queue_probabilities(&self) -> BTreeMap<String, f64> {
+        BTreeMap::new()
+    }
+
+    /// 5
+    /// В системе с неограниченной очередью отказов нет.
+    /// # Возвращаемое значение
+    /// Вероятность отказа, тип: `f64`.
+    fn calculate_rejection_probability(&self) -> f64 {
+        0.0
+    }
+
+    /// 6
+    /// Вычисляет среднее количество заявок, поступающих в систему за время T.
+    /// # Возвращаемое значение
+    /// Среднее количество заявок за указанный период времени, тип: `f64`.
+    fn calculate_average_incoming_requests_during_t(&self) -> f64 {
+        self.lambda_rate * self.time as f64
+    }
+
+    /// 7
+    /// Вычисляет среднее время обслуживания одной заявки.
+    /// # Возвращаемое значение
+    /// Среднее время, необходимое для обслуживания одной заявки, тип: `f64`.
+    fn calculate_average_service_time_per_request(&self) -> f64 {
+        1.0 / self.mu_rate
+    }
+
+    /// 8
+    /// Вычисляет среднее время обслуживания каналом заявок, поступивших за время T.
+    /// # Возвращаемое значение
+    /// Среднее время обслуживания заявок за время T, тип: `f64`.
+    fn average_service_time_per_channel_for_t(&self) -> f64 {
+        self.calculate_load_factor() * self.time as f64
+    }
+
+    /// 9
+    /// Вычисляет среднее число занятых каналов (равно ρ).
+    /// # Возвращаемое значение
+    /// Среднее количество занятых каналов, тип: `f64`.
+    fn calculate_average_busy_channels(&self) -> f64 {
+        self.calculate_load_factor().min(1.0)
+    }
+
+    /// 10
+    /// Вычисляет среднюю длину очереди по формуле Поллачека–Хинчина:
+    /// Lq = ρ²·(1 + c²) / (2·(1 - ρ)).
+    /// # Возвращаемое значение
+    /// Среднее количество заявок в очереди, бесконечность при ρ ≥ 1, тип: `f64`.
+    fn calculate_average_number_of_requests_in_queue(&self) -> f64 {
+        let ro = self.calculate_load_factor();
+        if ro >= 1.0 {
+            return f64::INFINITY;
+        }
+
+        ro.powi(2) * (1.0 + self.service_cv.powi(2)) / (2.0 * (1.0 - ro))
+    }
+
+    /// 12
+    /// Вычисляет среднее время пребывания заявки в очереди.
+    /// # Возвращаемое значение
+    /// Wq = Lq / λ, тип: `f64`.
+    fn calculate_average_waiting_time_in_queue(&self) -> f64 {
+        self.calculate_average_number_of_requests_in_queue() / self.lambda_rate
+    }
+
+    /// 13
+    /// Вычисляет общее количество заявок в системе.
+    /// # Возвращаемое значение
+    /// L = Lq + ρ, тип: `f64`.
+    fn calculate_total_number_of_requests(&self) -> f64 {
+        self.calculate_average_number_of_requests_in_queue() + self.calculate_load_factor()
+    }
+
+    /// 14
+    /// Вычисляет среднее время ожидания заявки в системе.
+    /// # Возвращаемое значение
+    /// Среднее время ожидания заявки в системе, тип: `f64`.
+    fn calculate_average_waiting_time(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue()
+    }
+
+    /// 15
+    /// Вычисляет среднее время пребывания заявки в системе.
+    /// # Возвращаемое значение
+    /// W = Wq + 1/μ, тип: `f64`.
+    fn calculate_average_time_in_system(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue() + self.calculate_average_service_time_per_request()
+    }
+}
@@ -0,0 +1,346 @@
+use std::error::Error;
+use nalgebra::{DMatrix, DVector};
+
+use crate::markov_chain;
+use crate::stationary_characteristics::StationaryCharacteristics;
+
+/// Распределение фазового типа: время обслуживания — время поглощения
+/// марковской цепи с экспоненциальными фазами.
+/// Обслуживание начинается в фазе j с вероятностью initial[j], фаза j длится
+/// экспоненциальное время с интенсивностью rates[j], после чего заявка переходит
+/// в фазу l с вероятностью transitions[j][l] или завершает обслуживание
+/// с вероятностью 1 - Σ_l transitions[j][l].
+#[derive(Debug, Clone)]
+pub struct PhaseTypeDistribution {
+    pub initial: Vec<f64>,          // Вероятности начальной фазы
+    pub rates: Vec<f64>,            // Интенсивности выхода из фаз
+    pub transitions: Vec<Vec<f64>>, // Вероятности переходов между фазами
+}
+
+impl PhaseTypeDistribution {
+    /// Создаёт распределение фазового типа.
+    /// # Параметры
+    /// * `initial` - Вероятности начальной фазы: неотрицательные, в сумме 1.
+    /// * `rates` - Положительные интенсивности выхода из фаз.
+    /// * `transitions` - Квадратная матрица вероятностей переходов между фазами: неотрицательные,
+    ///   в сумме по строке не больше 1.
+    /// # Возвращаемое значение
+    /// Распределение либо ошибка, если размеры не согласованы, вероятности некорректны или
+    /// из какой-либо фазы обслуживание не может завершиться, тип: `Result<PhaseTypeDistribution, Box<dyn Error>>`.
+    pub fn new(initial: Vec<f64>, rates: Vec<f64>, transitions: Vec<Vec<f64>>) -> Result<PhaseTypeDistribution, Box<dyn Error>> {
+        let phases = rates.len();
+        if phases == 0 {
+            return Err("не задано ни одной фазы обслуживания".into());
+        }
+        if initial.len() != phases || transitions.len() != phases || transitions.iter().any(|row| row.len() != phases) {
+            return Err(format!(
+                "для {} фаз нужны {} начальных вероятностей и матрица переходов {}×{}",
+                phases, phases, phases, phases
+            ).into());
+        }
+        if rates.iter().any(|rate| !(*rate > 0.0 && rate.is_finite())) {
+            return Err(format!("интенсивности фаз должны быть положительными: {:?}", rates).into());
+        }
+        if initial.iter().chain(transitions.iter().flatten()).any(|p| !p.is_finite() || *p < 0.0) {
+            return Err("вероятности начальной фазы и переходов должны быть неотрицательными".into());
+        }
+        let total: f64 = initial.iter().sum();
+        if (total - 1.0).abs() > 1e-9 {
+            return Err(format!("вероятности начальной фазы в сумме дают {}, а не 1", total).into());
+        }
+        if let Some(phase) = transitions.iter().position(|row| row.iter().sum::<f64>() > 1.0 + 1e-9) {
+            return Err(format!("вероятности переходов из фазы {} в сумме больше 1", phase).into());
+        }
+
+        let distribution = PhaseTypeDistribution {
+            initial,
+            rates,
+            transitions
+        };
+        distribution.moments()?;
+        Ok(distribution)
+    }
+
+    /// Распределение Эрланга: k последовательных фаз с интенсивностью kμ, среднее 1/μ.
+    pub fn erlang(phases: usize, mu_rate: f64) -> Result<PhaseTypeDistribution, Box<dyn Error>> {
+        let initial = (0..phases).map(|j| if j == 0 { 1.0 } else { 0.0 }).collect();
+        let transitions = (0..phases)
+            .map(|j| (0..phases).map(|l| if l == j + 1 { 1.0 } else { 0.0 }).collect())
+            .collect();
+
+        PhaseTypeDistribution::new(initial, vec![phases as f64 * mu_rate; phases], transitions)
+    }
+
+    /// Гиперэкспоненциальное распределение: с вероятностью probabilities[j]
+    /// обслуживание экспоненциально с интенсивностью rates[j].
+    pub fn hyperexponential(probabilities: Vec<f64>, rates: Vec<f64>) -> Result<PhaseTypeDistribution, Box<dyn Error>> {
+        let phases = rates.len();
+        PhaseTypeDistribution::new(probabilities, rates, vec![vec![0.0; phases]; phases])
+    }
+
+    /// Распределение Кокса: фазы проходятся последовательно, после фазы j
+    /// обслуживание продолжается с вероятностью continue_probabilities[j].
+    pub fn coxian(rates: Vec<f64>, continue_probabilities: Vec<f64>) -> Result<PhaseTypeDistribution, Box<dyn Error>> {
+        let phases = rates.len();
+        let initial = (0..phases).map(|j| if j == 0 { 1.0 } else { 0.0 }).collect();
+        let transitions = (0..phases)
+            .map(|j| (0..phases)
+                .map(|l| if l == j + 1 { continue_probabilities.get(j).copied().unwrap_or(0.0) } else { 0.0 })
+                .collect())
+            .collect();
+
+        PhaseTypeDistribution::new(initial, rates, transitions)
+    }
+
+    /// Количество фаз.
+    pub fn phases(&self) -> usize {
+        self.rates.len()
+    }
+
+    /// Вероятность завершить обслуживание после фазы `phase`.
+    pub fn exit_probability(&self, phase: usize) -> f64 {
+        (1.0 - self.transitions[phase].iter().sum::<f64>()).max(0.0)
+    }
+
+    /// Вычисляет начальные моменты: E[X] = α(-T)⁻¹1, E[X²] = 2α(-T)⁻²1,
+    /// где T — матрица интенсивностей переходов между фазами.
+    /// Матрица -T вырождена, если из какой-либо фазы нельзя завершить обслуживание.
+    fn moments(&self) -> Result<(f64, f64), Box<dyn Error>> {
+        let phases = self.phases();
+        let minus_t = DMatrix::from_fn(phases, phases, |j, l| {
+            let rate = self.rates[j];
+            if j == l { rate * (1.0 - self.transitions[j][l]) } else { -rate * self.transitions[j][l] }
+        });
+        let initial = DVector::from_vec(self.initial.clone());
+
+        let lu = minus_t.lu();
+        let first = lu.solve(&DVector::from_element(phases, 1.0))
+            .ok_or("из некоторых фаз обслуживание не может завершиться")?;
+        let second = lu.solve(&first)
+            .ok_or("из некоторых фаз обслуживание не может завершиться")?;
+
+        Ok((initial.dot(&first), 2.0 * initial.dot(&second)))
+    }
+
+    /// Вычисляет среднее время обслуживания.
+    /// # Возвращаемое значение
+    /// E[X], тип: `Result<f64, Box<dyn Error>>`.
+    pub fn mean(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.moments()?.0)
+    }
+
+    /// Вычисляет квадрат коэффициента вариации времени обслуживания.
+    /// # Возвращаемое значение
+    /// Var[X] / E[X]², тип: `Result<f64, Box<dyn Error>>`.
+    pub fn squared_cv(&self) -> Result<f64, Box<dyn Error>> {
+        let (first, second) = self.moments()?;
+        Ok(second / first.powi(2) - 1.0)
+    }
+}
+
+/// Состояние СМО с фазовым обслуживанием: число заявок в системе
+/// и число занятых каналов, находящихся в каждой фазе обслуживания.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhaseState {
+    pub customers: i32,   // Число заявок в системе
+    pub phases: Vec<i32>, // Число каналов, обслуживающих заявку в фазе j
+}
+
+/// СМО M/PH/s/n: пуассоновский поток, время обслуживания фазового типа.
+/// Каждое состояние по числу заявок раскрывается в состояния по фазам,
+/// поэтому по-прежнему применимы уравнения Колмогорова и метод Рунге–Кутты.
+pub struct PhaseTypeQueuingSystem {
+    pub lambda_rate: f64,                 // Интенсивность потока заявок
+    pub service: PhaseTypeDistribution,   // Распределение времени обслуживания
+    pub num_channels: i32,                // Количество каналов
+    pub queue_size: i32,                  // Ограничение на размер очереди
+    pub time: i32,                        // Время
+    pub num_iterations: i32,              // Количество итерации
+    pub step_size: f64                    // Шаг
+}
+
+impl PhaseTypeQueuingSystem {
+    pub fn new(lambda_rate: f64,
+               service: PhaseTypeDistribution,
+               num_channels: i32,
+               queue_size: i32,
+               time: i32,
+               num_iterations: i32,
+               step_size: f64
+    ) -> PhaseTypeQueuingSystem {
+
+        PhaseTypeQueuingSystem {
+            lambda_rate,
+            service,
+            num_channels,
+            queue_size,
+            time,
+            num_iterations,
+            step_size
+        }
+    }
+
+    fn capacity(&self) -> i32 {
+        self.num_channels + self.queue_size
+    }
+
+    /// Перечисляет переходы из состояния.
+    /// # Возвращаемое значение
+    /// Пары (новое состояние, интенсивность перехода), тип: `Vec<(PhaseState, f64)>`.
+    fn transitions(&self, state: &PhaseState) -> Vec<(PhaseState, f64)> {
+        let mut transitions = Vec::new();
+        let phases = self.service.phases();
+
+        // Поступление заявки: при свободном канале обслуживание сразу начинается в фазе j
+        if state.customers < self.capacity() {
+            if state.customers < self.num_channels {
+                for j in (0..phases).filter(|&j| self.service.initial[j] > 0.0) {
+                    let mut next = state.clone();
+                    next.customers += 1;
+                    next.phases[j] += 1;
+                    transitions.push((next, self.lambda_rate * self.service.initial[j]));
+                }
+            } else {
+                let mut next = state.clone();
+                next.customers += 1;
+                transitions.push((next, self.lambda_rate));
+            }
+        }
+
+        for j in (0..phases).filter(|&j| state.phases[j] > 0) {
+            let rate = state.phases[j] as f64 * self.service.rates[j];
+
+            // Переход заявки в следующую фазу
+            for l in (0..phases).filter(|&l| self.service.transitions[j][l] > 0.0) {
+                let mut next = state.clone();
+                next.phases[j] -= 1;
+                next.phases[l] += 1;
+                transitions.push((next, rate * self.service.transitions[j][l]));
+            }
+
+            // Завершение обслуживания, освободившийся канал берёт заявку из очереди
+            let exit_probability = self.service.exit_probability(j);
+            if exit_probability > 0.0 {
+                let mut next = state.clone();
+                next.customers -= 1;
+                next.phases[j] -= 1;
+                if state.customers > self.num_channels {
+                    for l in (0..phases).filter(|&l| self.service.initial[l] > 0.0) {
+                        let mut next = next.clone();
+                        next.phases[l] += 1;
+                        transitions.push((next, rate * exit_probability * self.service.initial[l]));
+                    }
+                } else {
+                    transitions.push((next, rate * exit_probability));
+                }
+            }
+        }
+
+        transitions
+    }
+
+    /// Перечисляет достижимые из пустой системы состояния обходом в ширину.
+    /// # Возвращаемое значение
+    /// Состояния в порядке нумерации строк матрицы Колмогорова, тип: `Vec<PhaseState>`.
+    pub fn states(&self) -> Vec<PhaseState> {
+        let empty = PhaseState { customers: 0, phases: vec![0; self.service.phases()] };
+        markov_chain::reachable_states(empty, |state| self.transitions(state))
+    }
+
+    /// Строит матрицу правых частей уравнений Колмогорова на пространстве состояний `states()`.
+    /// # Возвращаемое значение
+    /// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+    pub fn generate_kolmogorov_matrix(&self) -> DMatrix<f64> {
+        markov_chain::generator_matrix(&self.states(), |state| self.transitions(state))
+    }
+
+    /// Интегрирует уравнения Колмогорова, начиная с пустой системы,
+    /// и сводит результат к вероятностям числа заявок в системе.
+    /// # Возвращаемое значение
+    /// Пары (t, p(t)) с вероятностями S_0..S_{s+n}, тип: `Vec<(f64, DVector<f64>)>`.
+    pub fn integrate_system(&self) -> Vec<(f64, DVector<f64>)> {
+        let states = self.states();
+        let mut initial_state = DVector::zeros(states.len());
+        initial_state[0] = 1.0;
+
+        markov_chain::integrate(&self.generate_kolmogorov_matrix(), initial_state, self.step_size, self.num_iterations)
+            .iter()
+            .map(|(t, distribution)| (*t, self.customer_distribution(&states, distribution)))
+            .collect()
+    }
+
+    /// Сводит распределение по состояниям с фазами к распределению числа заявок в системе.
+    fn customer_distribution(&self, states: &[PhaseState], distribution: &DVector<f64>) -> DVector<f64> {
+        markov_chain::marginal_distribution(states, distribution, self.capacity() as usize + 1, |state| state.customers as usize)
+    }
+
+    /// Вычисляет стационарное распределение числа заявок в системе.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности S_0..S_{s+n}, тип: `Result<DVector<f64>, Box<dyn Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn Error>> {
+        let distribution = markov_chain::stationary_distribution(&self.generate_kolmogorov_matrix())?;
+        Ok(self.customer_distribution(&self.states(), &distribution))
+    }
+
+    /// Вычисляет характеристики СМО по числу заявок в системе.
+    /// Пропускная способность равна λ·(1 - P_{s+n}), средняя интенсивность обслуживания — 1 / E[X].
+    /// # Возвращаемое значение
+    /// Характеристики СМО, тип: `Result<StationaryCharacteristics, Box<dyn Error>>`.
+    pub fn characteristics(&self) -> Result<StationaryCharacteristics, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let throughput = self.lambda_rate * (1.0 - distribution[self.capacity() as usize]);
+
+        Ok(StationaryCharacteristics::new(
+            self.lambda_rate,
+            1.0 / self.service.mean()?,
+            self.num_channels,
+            self.queue_size,
+            self.time,
+            distribution,
+            throughput
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::queuing_system::QueuingSystem;
+    use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+    #[test]
+    fn single_phase_reduces_to_birth_death_model() {
+        let service = PhaseTypeDistribution::new(vec![1.0], vec![1.5], vec![vec![0.0]]).unwrap();
+        let phase_type = PhaseTypeQueuingSystem::new(4.0, service, 2, 3, 1, 100, 0.01).characteristics().unwrap();
+        let exponential = QueuingSystem::new(4.0, 1.5, 0.0, None, None, 2, 3, Config::empty_initial_state(2, 3), 1, 100, 0.01)
+            .stationary_characteristics()
+            .unwrap();
+
+        for i in 0..exponential.distribution.len() {
+            assert!((phase_type.distribution[i] - exponential.distribution[i]).abs() < 1e-9);
+        }
+        assert!((phase_type.calculate_average_waiting_time_in_queue() - exponential.calculate_average_waiting_time_in_queue()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn erlang_moments() {
+        let erlang = PhaseTypeDistribution::erlang(4, 2.0).unwrap();
+
+        assert!((erlang.mean().unwrap() - 0.5).abs() < 1e-12);
+        assert!((erlang.squared_cv().unwrap() - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rejects_invalid_distributions() {
+        // Размеры не согласованы
+        assert!(PhaseTypeDistribution::new(vec![1.0], vec![1.0, 2.0], vec![vec![0.0; 2]; 2]).is_err());
+        assert!(PhaseTypeDistribution::new(vec![0.5, 0.5], vec![1.0, 2.0], vec![vec![0.0; 2]]).is_err());
+        // Начальные вероятности не в сумме 1
+        assert!(PhaseTypeDistribution::new(vec![0.5, 0.2], vec![1.0, 2.0], vec![vec![0.0; 2]; 2]).is_err());
+        // Неположительная интенсивность
+        assert!(PhaseTypeDistribution::new(vec![1.0], vec![0.0], vec![vec![0.0]]).is_err());
+        // Фазы передают заявку друг другу и никогда не завершают обслуживание
+        assert!(PhaseTypeDistribution::new(vec![1.0, 0.0], vec![1.0, 2.0], vec![vec![0.0, 1.0], vec![1.0, 0.0]]).is_err());
+    }
+}
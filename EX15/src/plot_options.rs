@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+/// Формат файла графика.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+/// Язык подписей на графиках.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    Russian,
+    English,
+}
+
+/// Положение легенды на графике.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LegendPosition {
+    UpperLeft,
+    UpperRight,
+    LowerLeft,
+    LowerRight,
+}
+
+impl LegendPosition {
+    pub fn to_series_label_position(self) -> SeriesLabelPosition {
+        match self {
+            LegendPosition::UpperLeft => SeriesLabelPosition::UpperLeft,
+            LegendPosition::UpperRight => SeriesLabelPosition::UpperRight,
+            LegendPosition::LowerLeft => SeriesLabelPosition::LowerLeft,
+            LegendPosition::LowerRight => SeriesLabelPosition::LowerRight,
+        }
+    }
+}
+
+/// Заголовок и подписи осей, заданные для одного графика.
+#[derive(Debug, Clone, Default)]
+pub struct FigureLabels {
+    pub title: Option<String>,   // Заголовок
+    pub x_label: Option<String>, // Подпись оси абсцисс
+    pub y_label: Option<String>, // Подпись оси ординат
+}
+
+/// Настройки построения графиков. Графики сохраняются в каталог под своими именами,
+/// незаданные для графика заголовок и подписи осей берутся из графика на выбранном языке.
+#[derive(Debug, Clone)]
+pub struct PlotOptions {
+    pub directory: PathBuf,           // Каталог для графиков
+    pub size: Option<(u32, u32)>,     // Размер изображения; по умолчанию — собственный размер графика
+    pub format: ImageFormat,          // Формат файла
+    pub labels: HashMap<String, FigureLabels>, // Заголовки и подписи осей по имени графика
+    pub legend_position: LegendPosition, // Положение легенды
+    pub locale: Locale,               // Язык подписей
+    pub log_scale: bool,              // Логарифмическая шкала оси ординат у графиков вероятностей
+}
+
+impl Default for PlotOptions {
+    fn default() -> PlotOptions {
+        PlotOptions {
+            directory: PathBuf::from("."),
+            size: None,
+            format: ImageFormat::Png,
+            labels: HashMap::new(),
+            legend_position: LegendPosition::UpperLeft,
+            locale: Locale::English,
+            log_scale: false,
+        }
+    }
+}
+
+/// Формирует палитру из `count` различимых цветов: оттенки расставлены с шагом золотого сечения,
+/// поэтому соседние серии заметно отличаются при любом числе серий.
+/// # Параметры
+/// * `count` - Количество цветов.
+/// # Возвращаемое значение
+/// Цвета серий, тип: `Vec<HSLColor>`.
+pub fn palette(count: usize) -> Vec<HSLColor> {
+    (0..count)
+        .map(|i| HSLColor((i as f64 * 0.618_033_988_75).fract(), 0.75, if i % 2 == 0 { 0.45 } else { 0.35 }))
+        .collect()
+}
+
+/// График, который можно нарисовать на любом бэкенде plotters.
+pub trait Figure {
+    /// Имя файла графика без расширения.
+    fn default_name(&self) -> &str;
+
+    /// Собственный размер графика, если размер не задан в настройках.
+    fn default_size(&self) -> (u32, u32) {
+        (1024, 768)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static;
+}
+
+impl PlotOptions {
+    /// Разбирает настройки графиков из аргументов командной строки:
+    /// `--plot-dir=DIR`, `--plot-size=WxH`, `--plot-format=png|svg`, `--plot-title=NAME:TEXT`,
+    /// `--plot-x-label=NAME:TEXT`, `--plot-y-label=NAME:TEXT`, `--legend=upper-left|upper-right|lower-left|lower-right`,
+    /// `--locale=ru|en`, `--log-y`. Заголовок и подписи осей задаются для графика с именем файла NAME,
+    /// например `--plot-title=channels_states:Вероятности состояний`.
+    /// # Параметры
+    /// * `args` - Аргументы командной строки.
+    /// # Возвращаемое значение
+    /// Настройки графиков, тип: `Result<PlotOptions, Box<dyn Error>>`.
+    pub fn from_args(args: &[String]) -> Result<PlotOptions, Box<dyn Error>> {
+        let mut options = PlotOptions::default();
+        for arg in args {
+            if arg == "--log-y" {
+                options.log_scale = true;
+                continue;
+            }
+            let Some((key, value)) = arg.split_once('=') else { continue };
+            match key {
+                "--plot-dir" => options.directory = PathBuf::from(value),
+                "--plot-size" => {
+                    let (width, height) = value.split_once('x')
+                        .ok_or_else(|| format!("размер графика должен иметь вид WxH: {}", value))?;
+                    options.size = Some((width.parse()?, height.parse()?));
+                }
+                "--plot-format" => options.format = match value {
+                    "png" => ImageFormat::Png,
+                    "svg" => ImageFormat::Svg,
+                    _ => return Err(format!("неизвестный формат графика: {}", value).into()),
+                },
+                "--plot-title" | "--plot-x-label" | "--plot-y-label" => {
+                    let (figure, text) = value.split_once(':')
+                        .ok_or_else(|| format!("подпись графика должна иметь вид NAME:TEXT: {}", value))?;
+                    let labels = options.labels.entry(figure.to_string()).or_default();
+                    let label = match key {
+                        "--plot-title" => &mut labels.title,
+                        "--plot-x-label" => &mut labels.x_label,
+                        _ => &mut labels.y_label,
+                    };
+                    *label = Some(text.to_string());
+                }
+                "--legend" => options.legend_position = match value {
+                    "upper-left" => LegendPosition::UpperLeft,
+                    "upper-right" => LegendPosition::UpperRight,
+                    "lower-left" => LegendPosition::LowerLeft,
+                    "lower-right" => LegendPosition::LowerRight,
+                    _ => return Err(format!("неизвестное положение легенды: {}", value).into()),
+                },
+                "--locale" => options.locale = match value {
+                    "ru" => Locale::Russian,
+                    "en" => Locale::English,
+                    _ => return Err(format!("неизвестный язык подписей: {}", value).into()),
+                },
+                _ => {}
+            }
+        }
+        Ok(options)
+    }
+
+    /// Выбирает подпись на языке графиков.
+    /// # Параметры
+    /// * `russian` - Подпись на русском языке.
+    /// * `english` - Подпись на английском языке.
+    /// # Возвращаемое значение
+    /// Подпись, тип: `&str`.
+    pub fn text<'a>(&self, russian: &'a str, english: &'a str) -> &'a str {
+        match self.locale {
+            Locale::Russian => russian,
+            Locale::English => english,
+        }
+    }
+
+    /// Подпись графика `figure`, выбранная из `FigureLabels`, либо подпись по умолчанию на языке графиков.
+    fn label_or(&self, figure: &str, label: fn(&FigureLabels) -> &Option<String>, russian: &str, english: &str) -> String {
+        self.labels.get(figure)
+            .and_then(|labels| label(labels).clone())
+            .unwrap_or_else(|| self.text(russian, english).to_string())
+    }
+
+    /// Заголовок графика: заданный для него в настройках или подпись по умолчанию на языке графиков.
+    pub fn title_or(&self, figure: &str, russian: &str, english: &str) -> String {
+        self.label_or(figure, |labels| &labels.title, russian, english)
+    }
+
+    /// Подпись оси абсцисс: заданная для графика в настройках или подпись по умолчанию на языке графиков.
+    pub fn x_label_or(&self, figure: &str, russian: &str, english: &str) -> String {
+        self.label_or(figure, |labels| &labels.x_label, russian, english)
+    }
+
+    /// Подпись оси ординат: заданная для графика в настройках или подпись по умолчанию на языке графиков.
+    pub fn y_label_or(&self, figure: &str, russian: &str, english: &str) -> String {
+        self.label_or(figure, |labels| &labels.y_label, russian, english)
+    }
+
+    /// Путь к файлу графика `directory/name.{png,svg}`.
+    /// # Параметры
+    /// * `name` - Имя файла графика без расширения.
+    /// # Возвращаемое значение
+    /// Путь к файлу, тип: `PathBuf`.
+    pub fn output_path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{}.{}", name, self.format.extension()))
+    }
+
+    /// Рисует график в файл выбранного формата.
+    /// # Параметры
+    /// * `figure` - График.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn Error>>`.
+    pub fn render<F: Figure>(&self, figure: &F) -> Result<(), Box<dyn Error>> {
+        let path = self.output_path(figure.default_name());
+        let size = self.size.unwrap_or_else(|| figure.default_size());
+        match self.format {
+            ImageFormat::Png => {
+                let root = BitMapBackend::new(&path, size).into_drawing_area();
+                figure.draw(&root, self)?;
+                root.present()?;
+            }
+            ImageFormat::Svg => {
+                let root = SVGBackend::new(&path, size).into_drawing_area();
+                figure.draw(&root, self)?;
+                root.present()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Рисует график в строку SVG, например для встраивания в HTML-отчёт. Каталог и формат из настроек не используются.
+    /// # Параметры
+    /// * `figure` - График.
+    /// # Возвращаемое значение
+    /// Текст SVG, тип: `Result<String, Box<dyn Error>>`.
+    pub fn render_svg<F: Figure>(&self, figure: &F) -> Result<String, Box<dyn Error>> {
+        let size = self.size.unwrap_or_else(|| figure.default_size());
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, size).into_drawing_area();
+            figure.draw(&root, self)?;
+            root.present()?;
+        }
+        Ok(svg)
+    }
+}
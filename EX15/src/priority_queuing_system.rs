@@ -0,0 +1,297 @@
+use std::collections::VecDeque;
+use std::error::Error;
+
+use nalgebra::{DMatrix, DVector};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::markov_chain;
+
+/// Дисциплина обслуживания классов с приоритетами.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityDiscipline {
+    PreemptiveResume, // Заявка высшего приоритета прерывает обслуживание низшего, прерванная дообслуживается позже
+    NonPreemptive,    // Приоритет учитывается только при выборе заявки из очереди
+}
+
+/// Класс заявок. Классы упорядочены по убыванию приоритета: нулевой — самый срочный.
+#[derive(Debug, Clone, Copy)]
+pub struct PriorityClass {
+    pub lambda_rate: f64, // Интенсивность потока заявок класса
+    pub mu_rate: f64,     // Интенсивность обслуживания заявки класса одним каналом
+}
+
+/// Состояние СМО: число заявок каждого класса на обслуживании и в очереди.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PriorityState {
+    pub in_service: Vec<i32>,
+    pub waiting: Vec<i32>,
+}
+
+/// Характеристики одного класса заявок.
+#[derive(Debug, Clone, Copy)]
+pub struct ClassCharacteristics {
+    pub rejection_probability: f64,        // Вероятность отказа
+    pub average_number_in_queue: f64,      // Среднее число заявок класса в очереди
+    pub average_number_in_system: f64,     // Среднее число заявок класса в системе
+    pub average_waiting_time_in_queue: f64, // Среднее время ожидания в очереди
+    pub average_time_in_system: f64,       // Среднее время пребывания в системе
+}
+
+/// Многоклассовая СМО M/M/s/n с приоритетами.
+pub struct PriorityQueuingSystem {
+    pub classes: Vec<PriorityClass>,       // Классы заявок по убыванию приоритета
+    pub num_channels: i32,                 // Количество каналов
+    pub queue_size: i32,                   // Общее ограничение на размер очереди
+    pub discipline: PriorityDiscipline,    // Дисциплина обслуживания
+}
+
+/// Заявка в имитационной модели.
+#[derive(Debug, Clone, Copy)]
+struct Customer {
+    arrival: f64,       // Момент поступления
+    waited: f64,        // Накопленное время ожидания
+    queued_since: f64,  // Момент последней постановки в очередь
+}
+
+impl PriorityState {
+    fn total_in_service(&self) -> i32 {
+        self.in_service.iter().sum()
+    }
+
+    fn total_waiting(&self) -> i32 {
+        self.waiting.iter().sum()
+    }
+}
+
+impl PriorityQueuingSystem {
+    pub fn new(classes: Vec<PriorityClass>,
+               num_channels: i32,
+               queue_size: i32,
+               discipline: PriorityDiscipline
+    ) -> PriorityQueuingSystem {
+
+        PriorityQueuingSystem {
+            classes,
+            num_channels,
+            queue_size,
+            discipline
+        }
+    }
+
+    /// Класс низшего приоритета среди обслуживаемых, который может быть прерван заявкой класса `class`.
+    fn preempted_class(&self, in_service: &[i32], class: usize) -> Option<usize> {
+        match self.discipline {
+            PriorityDiscipline::PreemptiveResume => (class + 1..in_service.len()).rev().find(|&d| in_service[d] > 0),
+            PriorityDiscipline::NonPreemptive => None,
+        }
+    }
+
+    /// Перечисляет переходы из состояния.
+    /// # Возвращаемое значение
+    /// Пары (новое состояние, интенсивность перехода), тип: `Vec<(PriorityState, f64)>`.
+    fn transitions(&self, state: &PriorityState) -> Vec<(PriorityState, f64)> {
+        let mut transitions = Vec::new();
+        let busy = state.total_in_service();
+        let waiting = state.total_waiting();
+
+        for (class, parameters) in self.classes.iter().enumerate() {
+            // Поступление заявки класса
+            let mut next = state.clone();
+            if busy < self.num_channels {
+                next.in_service[class] += 1;
+                transitions.push((next, parameters.lambda_rate));
+            } else if waiting < self.queue_size {
+                match self.preempted_class(&state.in_service, class) {
+                    Some(preempted) => {
+                        next.in_service[preempted] -= 1;
+                        next.waiting[preempted] += 1;
+                        next.in_service[class] += 1;
+                    }
+                    None => next.waiting[class] += 1,
+                }
+                transitions.push((next, parameters.lambda_rate));
+            }
+
+            // Завершение обслуживания заявки класса, канал берёт заявку высшего приоритета из очереди
+            if state.in_service[class] > 0 {
+                let mut next = state.clone();
+                next.in_service[class] -= 1;
+                if let Some(head) = next.waiting.iter().position(|&w| w > 0) {
+                    next.waiting[head] -= 1;
+                    next.in_service[head] += 1;
+                }
+                transitions.push((next, state.in_service[class] as f64 * parameters.mu_rate));
+            }
+        }
+
+        transitions
+    }
+
+    /// Перечисляет достижимые из пустой системы состояния обходом в ширину.
+    /// # Возвращаемое значение
+    /// Состояния в порядке нумерации строк матрицы Колмогорова, тип: `Vec<PriorityState>`.
+    pub fn states(&self) -> Vec<PriorityState> {
+        let classes = self.classes.len();
+        let empty = PriorityState { in_service: vec![0; classes], waiting: vec![0; classes] };
+        markov_chain::reachable_states(empty, |state| self.transitions(state))
+    }
+
+    /// Строит матрицу правых частей уравнений Колмогорова на пространстве состояний `states()`.
+    /// # Возвращаемое значение
+    /// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+    pub fn generate_kolmogorov_matrix(&self) -> DMatrix<f64> {
+        markov_chain::generator_matrix(&self.states(), |state| self.transitions(state))
+    }
+
+    /// Вычисляет стационарное распределение по состояниям `states()`.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний, тип: `Result<DVector<f64>, Box<dyn Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn Error>> {
+        markov_chain::stationary_distribution(&self.generate_kolmogorov_matrix())
+    }
+
+    /// Вычисляет характеристики каждого класса по стационарному распределению.
+    /// Заявка любого класса получает отказ, если система заполнена (s + n заявок).
+    /// # Возвращаемое значение
+    /// Характеристики классов в порядке убывания приоритета, тип: `Result<Vec<ClassCharacteristics>, Box<dyn Error>>`.
+    pub fn calculate_class_characteristics(&self) -> Result<Vec<ClassCharacteristics>, Box<dyn Error>> {
+        let states = self.states();
+        let distribution = self.stationary_distribution()?;
+        let capacity = self.num_channels + self.queue_size;
+
+        let rejection_probability: f64 = states.iter()
+            .zip(distribution.iter())
+            .filter(|(state, _)| state.total_in_service() + state.total_waiting() == capacity)
+            .map(|(_, p)| p)
+            .sum();
+
+        Ok(self.classes.iter().enumerate().map(|(class, parameters)| {
+            let (in_queue, in_system) = states.iter()
+                .zip(distribution.iter())
+                .fold((0.0, 0.0), |(in_queue, in_system), (state, p)| (
+                    in_queue + state.waiting[class] as f64 * p,
+                    in_system + (state.waiting[class] + state.in_service[class]) as f64 * p,
+                ));
+            let accepted_rate = parameters.lambda_rate * (1.0 - rejection_probability);
+
+            ClassCharacteristics {
+                rejection_probability,
+                average_number_in_queue: in_queue,
+                average_number_in_system: in_system,
+                average_waiting_time_in_queue: in_queue / accepted_rate,
+                average_time_in_system: in_system / accepted_rate,
+            }
+        }).collect())
+    }
+
+    /// Имитационное моделирование системы для проверки результатов, полученных по цепи Маркова.
+    /// Статистика собирается после периода разогрева.
+    /// # Параметры
+    /// * `horizon` - Продолжительность моделирования.
+    /// * `warm_up` - Продолжительность периода разогрева.
+    /// * `seed` - Начальное значение генератора случайных чисел.
+    /// # Возвращаемое значение
+    /// Оценки характеристик классов, тип: `Vec<ClassCharacteristics>`.
+    pub fn simulate(&self, horizon: f64, warm_up: f64, seed: u64) -> Vec<ClassCharacteristics> {
+        let classes = self.classes.len();
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut in_service: Vec<Vec<Customer>> = vec![Vec::new(); classes];
+        let mut waiting: Vec<VecDeque<Customer>> = vec![VecDeque::new(); classes];
+
+        let mut arrivals = vec![0usize; classes];
+        let mut rejections = vec![0usize; classes];
+        let mut departures = vec![0usize; classes];
+        let mut total_waiting_time = vec![0.0; classes];
+        let mut total_time_in_system = vec![0.0; classes];
+        let mut queue_area = vec![0.0; classes];
+        let mut system_area = vec![0.0; classes];
+
+        let mut t = 0.0;
+        while t < horizon {
+            let service_rates: Vec<f64> = (0..classes)
+                .map(|c| in_service[c].len() as f64 * self.classes[c].mu_rate)
+                .collect();
+            let arrival_rate: f64 = self.classes.iter().map(|c| c.lambda_rate).sum();
+            let total_rate = arrival_rate + service_rates.iter().sum::<f64>();
+
+            let dt = -rng.gen::<f64>().ln() / total_rate;
+            if t + dt > warm_up {
+                let observed = (t + dt).min(horizon) - t.max(warm_up);
+                for c in 0..classes {
+                    queue_area[c] += waiting[c].len() as f64 * observed;
+                    system_area[c] += (waiting[c].len() + in_service[c].len()) as f64 * observed;
+                }
+            }
+            t += dt;
+            let measured = t > warm_up;
+
+            let mut event = rng.gen::<f64>() * total_rate;
+            let arrival_class = self.classes.iter().position(|c| {
+                event -= c.lambda_rate;
+                event < 0.0
+            });
+
+            match arrival_class {
+                Some(class) => {
+                    let customer = Customer { arrival: t, waited: 0.0, queued_since: t };
+                    let busy: usize = in_service.iter().map(Vec::len).sum();
+                    let queued: usize = waiting.iter().map(VecDeque::len).sum();
+                    if measured {
+                        arrivals[class] += 1;
+                    }
+
+                    if busy < self.num_channels as usize {
+                        in_service[class].push(customer);
+                    } else if queued < self.queue_size as usize {
+                        let counts: Vec<i32> = in_service.iter().map(|v| v.len() as i32).collect();
+                        if let Some(preempted) = self.preempted_class(&counts, class) {
+                            let index = rng.gen_range(0..in_service[preempted].len());
+                            let mut interrupted = in_service[preempted].swap_remove(index);
+                            interrupted.queued_since = t;
+                            waiting[preempted].push_front(interrupted);
+                            in_service[class].push(customer);
+                        } else {
+                            waiting[class].push_back(customer);
+                        }
+                    } else if measured {
+                        rejections[class] += 1;
+                    }
+                }
+                None => {
+                    let class = service_rates.iter().position(|rate| {
+                        event -= rate;
+                        event < 0.0
+                    }).unwrap_or(classes - 1);
+                    if in_service[class].is_empty() {
+                        continue;
+                    }
+
+                    let index = rng.gen_range(0..in_service[class].len());
+                    let finished = in_service[class].swap_remove(index);
+                    if measured && finished.arrival > warm_up {
+                        departures[class] += 1;
+                        total_waiting_time[class] += finished.waited;
+                        total_time_in_system[class] += t - finished.arrival;
+                    }
+
+                    if let Some(head) = waiting.iter().position(|queue| !queue.is_empty()) {
+                        let mut next = waiting[head].pop_front().expect("non-empty queue");
+                        next.waited += t - next.queued_since;
+                        in_service[head].push(next);
+                    }
+                }
+            }
+        }
+
+        let observed_time = horizon - warm_up;
+        (0..classes).map(|c| ClassCharacteristics {
+            rejection_probability: rejections[c] as f64 / arrivals[c].max(1) as f64,
+            average_number_in_queue: queue_area[c] / observed_time,
+            average_number_in_system: system_area[c] / observed_time,
+            average_waiting_time_in_queue: total_waiting_time[c] / departures[c].max(1) as f64,
+            average_time_in_system: total_time_in_system[c] / departures[c].max(1) as f64,
+        }).collect()
+    }
+}
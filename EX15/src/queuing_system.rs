@@ -1,29 +1,53 @@
 
 use std::sync::Arc;
-use nalgebra::{DMatrix, DVector};
+use nalgebra::DVector;
 use std::cmp::Ordering::{Equal, Greater, Less};
 
+use plotters::coord::Shift;
 use plotters::prelude::*;
-use crate::config::{QUEUING_SYSTEM_CONFIG};
+use plotters::coord::ranged1d::ValueFormatter;
+use plotters::coord::types::RangedCoordf64;
+use crate::batch_arrivals::{BatchArrivals, BatchRejectionPolicy};
+use crate::config::Config;
+use crate::plot_options::{palette, Figure, PlotOptions};
+use crate::markov_chain::{self, kolmogorov_matrix_to_dmatrix, FirstPassageTime};
+use crate::sensitivity::SensitivityParameter;
+use crate::state_graph::{format_rate, StateGraph};
+use crate::stationary_characteristics::StationaryCharacteristics;
+use crate::transient_metrics::TransientMetrics;
 
 
+/// Нижняя и верхняя границы полосы вероятностей состояний в моменты 0, h, 2h, ...
+type StateBand<'a> = (&'a [(f64, DVector<f64>)], &'a [(f64, DVector<f64>)]);
+
+/// Наибольшая длина очереди, до которой `truncate_infinite_queue` подбирает усечение.
+const MAX_TRUNCATED_QUEUE_SIZE: usize = 10_000;
+
+#[derive(Clone)]
 pub struct QueuingSystem {
-    pub lambda_rate: i32, // Интенсивность потока заявок
-    pub mu_rate: i32,     // Интенсивность обработки одним офицером
+    pub lambda_rate: f64, // Интенсивность потока заявок
+    pub mu_rate: f64,     // Интенсивность обработки одним офицером
+    pub abandonment_rate: f64, // Интенсивность ухода из очереди одной ожидающей заявки
+    pub balking: Option<fn(i32) -> f64>, // Вероятность присоединиться к очереди из k заявок
+    pub batch_arrivals: Option<BatchArrivals>, // Распределение размера группы поступающих заявок
     pub num_channels: i32, // Количество офицеров
     pub queue_size: i32, // Ограничение на размер очереди
-    pub initial_state: Arc<Vec<(&'static str, i32)>>, // Начальное состояние
+    pub initial_state: Arc<Vec<(String, i32)>>, // Начальное состояние
     pub time: i32, // Время
     pub num_iterations: i32, // Количество итерации
     pub step_size: f64 // Шаг
 }
 
 impl QueuingSystem {
-    pub fn new(lambda_rate: i32,
-               mu_rate: i32,
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               abandonment_rate: f64,
+               balking: Option<fn(i32) -> f64>,
+               batch_arrivals: Option<BatchArrivals>,
                num_channels: i32,
                queue_size: i32,
-               initial_state: Arc<Vec<(&'static str, i32)>>,
+               initial_state: Arc<Vec<(String, i32)>>,
                time: i32,
                num_iterations: i32,
                step_size: f64
@@ -33,6 +57,9 @@ impl QueuingSystem {
         QueuingSystem {
             lambda_rate,
             mu_rate,
+            abandonment_rate,
+            balking,
+            batch_arrivals,
             num_channels,
             queue_size,
             initial_state,
@@ -42,12 +69,496 @@ impl QueuingSystem {
         }
     }
 
-    pub fn plot_state_graph(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let dimensions = (1024, 768);
-        let root_area = BitMapBackend::new("queuing_system_states.png", dimensions).into_drawing_area();
-        root_area.fill(&WHITE)?;
+    /// Рисует граф состояний S_0..S_{s+n} процесса гибели и размножения.
+    /// Дуги и их подписи берутся из матрицы уравнений Колмогорова, поэтому совпадают
+    /// с переходами, используемыми при интегрировании. При групповых поступлениях
+    /// граф перестаёт быть цепочкой и рисуется по окружности.
+    /// # Параметры
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn std::error::Error>>`.
+    pub fn plot_state_graph(&self, options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>> {
+        let graph = self.state_graph();
+        if !graph.is_birth_death() {
+            return graph.plot("queuing_system_states", options);
+        }
+        options.render(&StateGraphFigure { system: self, graph })
+    }
+
+    /// Рисует граф состояний, как `plot_state_graph`, в строку SVG.
+    /// # Параметры
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Текст SVG, тип: `Result<String, Box<dyn std::error::Error>>`.
+    pub fn state_graph_svg(&self, options: &PlotOptions) -> Result<String, Box<dyn std::error::Error>> {
+        let graph = self.state_graph();
+        if !graph.is_birth_death() {
+            return graph.to_svg(options);
+        }
+        options.render_svg(&StateGraphFigure { system: self, graph })
+    }
+
+    /// Строит граф состояний S_0..S_{s+n} с интенсивностями переходов
+    /// в числовой и символьной записи («3μ = 15»).
+    /// # Возвращаемое значение
+    /// Граф состояний, тип: `StateGraph`.
+    pub fn state_graph(&self) -> StateGraph {
+        let components = self.kolmogorov_matrix_components();
+        let mut graph = StateGraph::from_matrix(&kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix()), self.state_labels());
+        for transition in &mut graph.transitions {
+            transition.symbol = Some(Self::transition_symbol(&components, transition.from, transition.to));
+        }
+        graph
+    }
+
+    /// Раскладывает матрицу уравнений Колмогорова по интенсивностям: A = λ·A_λ + μ·A_μ + θ·A_θ.
+    /// Коэффициенты A_λ, A_μ и A_θ — матрицы системы, в которой одна интенсивность равна 1, а остальные — 0.
+    /// Слагаемые с нулевой интенсивностью отбрасываются, чтобы не попадать в подписи дуг и уравнения.
+    pub fn kolmogorov_matrix_components(&self) -> Vec<(&'static str, Vec<Vec<f64>>)> {
+        let abandonment_system = QueuingSystem {
+            lambda_rate: 0.0,
+            mu_rate: 0.0,
+            abandonment_rate: 1.0,
+            ..self.clone()
+        };
+        [
+            ("λ", self.lambda_rate, self.kolmogorov_matrix_derivative(SensitivityParameter::Lambda).unwrap_or_default()),
+            ("μ", self.mu_rate, self.kolmogorov_matrix_derivative(SensitivityParameter::Mu).unwrap_or_default()),
+            ("θ", self.abandonment_rate, abandonment_system.generate_kolmogorov_matrix()),
+        ]
+            .into_iter()
+            .filter(|&(_, rate, _)| rate != 0.0)
+            .map(|(symbol, _, matrix)| (symbol, matrix))
+            .collect()
+    }
+
+    /// Символьная запись интенсивности перехода из S_from в S_to, например «λ», «3μ» или «2μ + θ».
+    fn transition_symbol(components: &[(&'static str, Vec<Vec<f64>>)], from: usize, to: usize) -> String {
+        let terms: Vec<String> = components.iter()
+            .map(|(symbol, matrix)| (symbol, matrix[to][from]))
+            .filter(|&(_, coefficient)| coefficient != 0.0)
+            .map(|(symbol, coefficient)| {
+                if coefficient == 1.0 { symbol.to_string() } else { format!("{}{}", format_rate(coefficient), symbol) }
+            })
+            .collect();
+        if terms.is_empty() { "0".to_string() } else { terms.join(" + ") }
+    }
+
+    /// Вероятность того, что заявка, заставшая систему в состоянии S_i, присоединится к ней.
+    /// Пока есть свободный канал (i < s), заявка сразу начинает обслуживаться и присоединяется
+    /// всегда; иначе вероятность задаётся функцией b(k) от числа ожидающих k = i - s, без неё равна 1.
+    /// # Параметры
+    /// * `i` - Номер состояния (число заявок в системе).
+    /// # Возвращаемое значение
+    /// Вероятность присоединения, тип: `f64`.
+    pub fn join_probability(&self, i: usize) -> f64 {
+        let num_channels = self.num_channels as usize;
+        if i < num_channels {
+            return 1.0;
+        }
+        let waiting = (i - num_channels) as i32;
+        self.balking.map_or(1.0, |balking| balking(waiting).clamp(0.0, 1.0))
+    }
+
+    /// Интенсивность перехода из состояния S_from в S_to (to > from) за счёт поступления заявок.
+    /// Одиночная заявка переводит систему в S_{from+1} с интенсивностью λ·b(k); группа из m заявок —
+    /// в S_{from+m} с интенсивностью λ·b(k)·g_m. Не помещающаяся группа либо заполняет систему
+    /// (частичный приём), либо получает отказ целиком.
+    /// # Параметры
+    /// * `from` - Исходное состояние.
+    /// * `to` - Новое состояние.
+    /// # Возвращаемое значение
+    /// Интенсивность перехода, тип: `f64`.
+    pub fn arrival_rate(&self, from: usize, to: usize) -> f64 {
+        let queue_max_index = (self.num_channels + self.queue_size) as usize;
+        if to <= from || to > queue_max_index {
+            return 0.0;
+        }
+
+        let rate = self.lambda_rate * self.join_probability(from);
+        match &self.batch_arrivals {
+            None => if to == from + 1 { rate } else { 0.0 },
+            Some(batch) => {
+                let probability: f64 = batch.size_probabilities.iter()
+                    .enumerate()
+                    .filter(|(k, _)| {
+                        let target = from + k + 1;
+                        target == to || (to == queue_max_index && target > to && batch.policy == BatchRejectionPolicy::Partial)
+                    })
+                    .fold(0.0, |acc, (_, g)| acc + g);
+                rate * probability
+            }
+        }
+    }
+
+    /// Суммарная интенсивность выхода из состояния S_i за счёт поступления заявок.
+    /// Без групповых поступлений это интенсивность перехода в S_{i+1}: λ·b(k).
+    /// # Параметры
+    /// * `i` - Номер состояния (число заявок в системе).
+    /// # Возвращаемое значение
+    /// Интенсивность перехода, тип: `f64`.
+    pub fn birth_rate(&self, i: usize) -> f64 {
+        let queue_max_index = (self.num_channels + self.queue_size) as usize;
+        (i + 1..=queue_max_index).map(|to| self.arrival_rate(i, to)).sum()
+    }
+
+    /// Интенсивность перехода из состояния S_i в S_{i-1}: min(i, s)·μ + k·θ,
+    /// где k = max(i - s, 0) — число заявок в очереди.
+    /// # Параметры
+    /// * `i` - Номер состояния (число заявок в системе).
+    /// # Возвращаемое значение
+    /// Интенсивность перехода, тип: `f64`.
+    pub fn death_rate(&self, i: usize) -> f64 {
+        let num_channels = self.num_channels as usize;
+        let busy_channels = i.min(num_channels);
+        let waiting = i.saturating_sub(num_channels);
+        busy_channels as f64 * self.mu_rate + waiting as f64 * self.abandonment_rate
+    }
+
+    /// Коэффициент использования каналов ρ = λ·E[размер группы] / (sμ).
+    /// При ρ ≥ 1 каналы не успевают обслуживать поток: очередь без ограничения росла бы неограниченно.
+    /// # Возвращаемое значение
+    /// Коэффициент использования, тип: `f64`.
+    pub fn utilization(&self) -> f64 {
+        let offered_rate = self.lambda_rate * self.batch_arrivals.as_ref().map_or(1.0, BatchArrivals::mean_size);
+        offered_rate / (self.num_channels as f64 * self.mu_rate)
+    }
+
+    pub fn generate_kolmogorov_matrix(&self) -> Vec<Vec<f64>> {
+        let queue_max_index = (self.num_channels + self.queue_size) as usize;
+        let number_of_states = queue_max_index + 1;
+
+        (0..number_of_states).map(|i| {
+            (0..number_of_states).map(|j| {
+                match i.cmp(&j) {
+                    Equal => - (self.birth_rate(i) + self.death_rate(i)),
+                    Less => if j == i + 1 { self.death_rate(j) } else { 0.0 },
+                    Greater => self.arrival_rate(j, i),
+                }
+            }).collect()
+        }).collect()
+    }
+
+    /// Подбирает длину очереди, при которой усечённая модель приближает систему
+    /// с неограниченной очередью (Erlang-A) с заданной точностью.
+    /// Хвост распределения оценивается сверху геометрической прогрессией со знаменателем
+    /// λ / (sμ + kθ), поэтому требуется θ > 0 либо λ < sμ. Функция отказа от присоединения
+    /// b(k) ≤ 1 только уменьшает хвост, поэтому оценка остаётся верной и для неё.
+    /// Если точность не достигнута при длине очереди `MAX_TRUNCATED_QUEUE_SIZE`
+    /// (например, при μ = 0 хвост убывает слишком медленно), возвращается ошибка.
+    /// # Параметры
+    /// * `tolerance` - Допустимая суммарная вероятность отброшенных состояний.
+    /// # Возвращаемое значение
+    /// Усечённая система и верхняя оценка погрешности, тип: `Result<(QueuingSystem, f64), Box<dyn std::error::Error>>`.
+    pub fn truncate_infinite_queue(&self, tolerance: f64) -> Result<(QueuingSystem, f64), Box<dyn std::error::Error>> {
+        let num_channels = self.num_channels as usize;
+        if self.batch_arrivals.is_some() {
+            return Err("усечение очереди поддерживается только для одиночных поступлений заявок".into());
+        }
+        if self.abandonment_rate <= 0.0 && self.lambda_rate >= self.num_channels as f64 * self.mu_rate {
+            return Err("система с бесконечной очередью неустойчива: нужно θ > 0 или λ < sμ".into());
+        }
+
+        // Ненормированные стационарные вероятности w_i = Π λ·b(k) / d(j)
+        let mut weight = 1.0;
+        let mut total = 1.0;
+        let mut i = 0;
+        while i <= num_channels + MAX_TRUNCATED_QUEUE_SIZE {
+            let ratio = self.lambda_rate / self.death_rate(i + 1);
+            if i >= num_channels && ratio < 1.0 {
+                let error_bound = weight * ratio / (1.0 - ratio) / total;
+                if error_bound <= tolerance {
+                    let queue_size = (i - num_channels) as i32;
+                    let truncated = QueuingSystem {
+                        queue_size,
+                        initial_state: Config::empty_initial_state(self.num_channels, queue_size),
+                        ..self.clone()
+                    };
+                    return Ok((truncated, error_bound));
+                }
+            }
+            weight *= ratio * self.join_probability(i);
+            total += weight;
+            i += 1;
+        }
+        Err(format!(
+            "не удалось достичь точности {:e} при длине очереди до {}: проверьте, что μ > 0",
+            tolerance, MAX_TRUNCATED_QUEUE_SIZE
+        ).into())
+    }
+
+    fn initial_state_to_dvector(initial_state: Arc<Vec<(String, i32)>>) -> DVector<f64> {
+        let values: Vec<f64> = initial_state
+            .iter()
+            .map(|(_key, value)| *value as f64)
+            .collect();
+
+        DVector::from_vec(values)
+    }
+
+    // Интегрирование системы уравнений: пары (t, p(t)) в моменты 0, h, 2h, ...
+    pub fn integrate_system(&self) -> Vec<(f64, DVector<f64>)> {
+        let matrix = kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
+        let initial_state_vec = Self::initial_state_to_dvector(Arc::clone(&self.initial_state));
+
+        markov_chain::integrate(&matrix, initial_state_vec, self.step_size, self.num_iterations)
+    }
+
+    /// Производная матрицы уравнений Колмогорова по интенсивности λ или μ.
+    /// Интенсивности переходов линейны по λ и μ, поэтому производная равна матрице системы,
+    /// в которой дифференцируемая интенсивность равна 1, а остальные — 0.
+    /// # Параметры
+    /// * `parameter` - Параметр дифференцирования.
+    /// # Возвращаемое значение
+    /// Матрица ∂A/∂θ или `None` для дискретных параметров s и n, тип: `Option<Vec<Vec<f64>>>`.
+    pub fn kolmogorov_matrix_derivative(&self, parameter: SensitivityParameter) -> Option<Vec<Vec<f64>>> {
+        let (lambda_rate, mu_rate) = match parameter {
+            SensitivityParameter::Lambda => (1.0, 0.0),
+            SensitivityParameter::Mu => (0.0, 1.0),
+            SensitivityParameter::Channels | SensitivityParameter::Queue => return None,
+        };
+        let unit_system = QueuingSystem {
+            lambda_rate,
+            mu_rate,
+            abandonment_rate: 0.0,
+            ..self.clone()
+        };
+        Some(unit_system.generate_kolmogorov_matrix())
+    }
+
+    /// Интегрирует кривые чувствительности dp_i(t)/dθ вероятностей состояний к λ или μ.
+    /// # Параметры
+    /// * `parameter` - Параметр дифференцирования.
+    /// # Возвращаемое значение
+    /// Пары (t, dp(t)/dθ) в моменты 0, h, 2h, ... или `None` для s и n,
+    /// тип: `Option<Vec<(f64, DVector<f64>)>>`.
+    pub fn integrate_sensitivity(&self, parameter: SensitivityParameter) -> Option<Vec<(f64, DVector<f64>)>> {
+        let derivative = kolmogorov_matrix_to_dmatrix(self.kolmogorov_matrix_derivative(parameter)?);
+        let matrix = kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
+        let initial_state_vec = Self::initial_state_to_dvector(Arc::clone(&self.initial_state));
+
+        Some(markov_chain::integrate_sensitivity(&matrix, &derivative, initial_state_vec, self.step_size, self.num_iterations))
+    }
+
+    /// Вычисляет характеристики СМО в каждый момент траектории `integrate_system`.
+    /// Вероятность потери — доля поступающих заявок, не принятых в систему (из-за заполненной
+    /// очереди, отказа от присоединения или непоместившейся группы): 1 - Σ a_i·p_i(t) / (λ·E[размер группы]),
+    /// где a_i — интенсивность принятия заявок в состоянии S_i.
+    /// # Параметры
+    /// * `states` - Пары (t, p(t)) траектории `integrate_system`.
+    /// # Возвращаемое значение
+    /// Характеристики во времени, тип: `TransientMetrics`.
+    pub fn transient_metrics(&self, states: &[(f64, DVector<f64>)]) -> TransientMetrics {
+        let num_channels = self.num_channels as usize;
+        let queue_max_index = (self.num_channels + self.queue_size) as usize;
+        let offered_rate = self.lambda_rate * self.batch_arrivals.as_ref().map_or(1.0, BatchArrivals::mean_size);
+        let accepted_rates: Vec<f64> = (0..=queue_max_index)
+            .map(|i| (i + 1..=queue_max_index).map(|to| (to - i) as f64 * self.arrival_rate(i, to)).sum())
+            .collect();
+        let expectation = |state: &DVector<f64>, value: &dyn Fn(usize) -> f64| -> f64 {
+            state.iter().enumerate().map(|(i, p)| value(i) * p).sum()
+        };
+
+        TransientMetrics {
+            times: states.iter().map(|(t, _)| *t).collect(),
+            number_in_system: states.iter().map(|(_, state)| expectation(state, &|i| i as f64)).collect(),
+            queue_length: states.iter().map(|(_, state)| expectation(state, &|i| i.saturating_sub(num_channels) as f64)).collect(),
+            busy_channels: states.iter().map(|(_, state)| expectation(state, &|i| i.min(num_channels) as f64)).collect(),
+            rejection_probability: states.iter()
+                .map(|(_, state)| (1.0 - expectation(state, &|i| accepted_rates[i]) / offered_rate).max(0.0))
+                .collect(),
+            throughput: states.iter().map(|(_, state)| expectation(state, &|i| i.min(num_channels) as f64 * self.mu_rate)).collect(),
+        }
+    }
+
+    /// Вычисляет среднее и дисперсию времени до первого попадания в состояния `targets`
+    /// из начального состояния системы.
+    /// # Параметры
+    /// * `targets` - Номера целевых состояний S_i.
+    /// # Возвращаемое значение
+    /// Моменты времени достижения, тип: `Result<FirstPassageTime, Box<dyn std::error::Error>>`.
+    pub fn first_passage_time(&self, targets: &[usize]) -> Result<FirstPassageTime, Box<dyn std::error::Error>> {
+        let matrix = kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
+        let initial_state_vec = Self::initial_state_to_dvector(Arc::clone(&self.initial_state));
+
+        markov_chain::first_passage_time(&matrix, &initial_state_vec, targets)
+    }
+
+    /// Вычисляет функцию распределения времени до первого попадания в состояния `targets`
+    /// на сетке интегрирования системы.
+    /// # Параметры
+    /// * `targets` - Номера целевых состояний S_i.
+    /// # Возвращаемое значение
+    /// Пары (t, P(τ ≤ t)) в моменты 0, h, 2h, ..., тип: `Vec<(f64, f64)>`.
+    pub fn first_passage_cdf(&self, targets: &[usize]) -> Vec<(f64, f64)> {
+        let matrix = kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
+        let initial_state_vec = Self::initial_state_to_dvector(Arc::clone(&self.initial_state));
+
+        markov_chain::first_passage_cdf(&matrix, initial_state_vec, targets, self.step_size, self.num_iterations)
+    }
+
+    /// Формирует интенсивности затрат по состояниям: занятые каналы и ожидающие в очереди заявки.
+    /// # Параметры
+    /// * `busy_channel_cost` - Затраты на один занятый канал в единицу времени.
+    /// * `waiting_cost` - Штраф за одну ожидающую заявку в единицу времени.
+    /// # Возвращаемое значение
+    /// Затраты в состояниях S_0..S_{s+n}: min(i, s)·c_к + max(i - s, 0)·c_о, тип: `DVector<f64>`.
+    pub fn staffing_cost_rates(&self, busy_channel_cost: f64, waiting_cost: f64) -> DVector<f64> {
+        let s = self.num_channels as usize;
+        DVector::from_fn(self.initial_state.len(), |i, _| {
+            i.min(s) as f64 * busy_channel_cost + i.saturating_sub(s) as f64 * waiting_cost
+        })
+    }
+
+    /// Вычисляет стационарное распределение вероятностей состояний S_0..S_{s+n}
+    /// численным решением системы уравнений Колмогорова.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний, тип: `Result<DVector<f64>, Box<dyn std::error::Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn std::error::Error>> {
+        let matrix = kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
+        markov_chain::stationary_distribution(&matrix)
+    }
+
+    /// Вычисляет характеристики СМО по численному стационарному распределению с учётом
+    /// ограничения очереди, уходов из очереди, отказа от присоединения и групповых поступлений.
+    /// Интенсивность входящего потока заявок равна λ·E[размер группы], пропускная способность
+    /// X = Σ min(i, s)·μ·p_i, интенсивность потока принятых заявок X + θ·Lq.
+    /// # Возвращаемое значение
+    /// Характеристики СМО, тип: `Result<StationaryCharacteristics, Box<dyn std::error::Error>>`.
+    pub fn stationary_characteristics(&self) -> Result<StationaryCharacteristics, Box<dyn std::error::Error>> {
+        let distribution = self.stationary_distribution()?;
+        let num_channels = self.num_channels as usize;
+        let customer_rate = self.lambda_rate * self.batch_arrivals.as_ref().map_or(1.0, BatchArrivals::mean_size);
+        let (throughput, queue_length) = distribution.iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(throughput, queue_length), (i, p)| (
+                throughput + i.min(num_channels) as f64 * self.mu_rate * p,
+                queue_length + i.saturating_sub(num_channels) as f64 * p,
+            ));
+
+        Ok(StationaryCharacteristics::new(
+            customer_rate,
+            self.mu_rate,
+            self.num_channels,
+            self.queue_size,
+            self.time,
+            distribution,
+            throughput
+        ).with_admitted_rate(throughput + self.abandonment_rate * queue_length))
+    }
+
+
+    /// Подписи состояний S_0..S_{s+n} из начального состояния.
+    pub fn state_labels(&self) -> Vec<String> {
+        self.initial_state.iter().map(|(name, _)| name.clone()).collect()
+    }
 
-        let states = self.initial_state.len() as i32;
+    /// Строит графики вероятностей состояний во времени с отметками стационарных вероятностей.
+    /// # Параметры
+    /// * `states` - Пары (t, p(t)) траектории `integrate_system`.
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn std::error::Error>>`.
+    pub fn plot_states(&self, states: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>> {
+        options.render(&self.states_figure(states, options)?)
+    }
+
+    /// Рисует графики вероятностей состояний, как `plot_states`, в строку SVG.
+    /// # Параметры
+    /// * `states` - Пары (t, p(t)) траектории `integrate_system`.
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Текст SVG, тип: `Result<String, Box<dyn std::error::Error>>`.
+    pub fn states_svg(&self, states: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<String, Box<dyn std::error::Error>> {
+        options.render_svg(&self.states_figure(states, options)?)
+    }
+
+    /// График вероятностей состояний с отметками стационарных вероятностей для `plot_states` и `states_svg`.
+    fn states_figure<'a>(&self, states: &'a [(f64, DVector<f64>)], options: &PlotOptions) -> Result<TrajectoryFigure<'a>, Box<dyn std::error::Error>> {
+        Ok(TrajectoryFigure {
+            name: "channels_states",
+            title: ("Вероятности состояний во времени", "System States Over Time"),
+            y_label: ("Вероятность", "Probability"),
+            labels: self.state_labels(),
+            states,
+            band: None,
+            stationary: Some(self.stationary_distribution()?),
+            log_scale: options.log_scale,
+        })
+    }
+
+    /// Строит медиану вероятностей состояний с закрашенной полосой между квантилями,
+    /// полученными распространением неопределённости λ и μ.
+    /// # Параметры
+    /// * `median` - Пары (t, медиана p(t)) в моменты траектории `integrate_system`.
+    /// * `lower` - Нижний квантиль вероятностей состояний.
+    /// * `upper` - Верхний квантиль вероятностей состояний.
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn std::error::Error>>`.
+    pub fn plot_states_with_band(&self, median: &[(f64, DVector<f64>)], lower: &[(f64, DVector<f64>)], upper: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>> {
+        options.render(&TrajectoryFigure {
+            name: "channels_states_band",
+            title: ("Вероятности состояний во времени", "System States Over Time"),
+            y_label: ("Вероятность", "Probability"),
+            labels: self.state_labels(),
+            states: median,
+            band: Some((lower, upper)),
+            stationary: None,
+            log_scale: options.log_scale,
+        })
+    }
+
+    /// Строит кривые чувствительности dp_i(t)/dθ вероятностей состояний.
+    /// Производные меняют знак, поэтому логарифмическая шкала к ним не применяется.
+    /// # Параметры
+    /// * `sensitivities` - Пары (t, dp(t)/dθ) траектории `integrate_sensitivity`.
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn std::error::Error>>`.
+    pub fn plot_state_sensitivities(&self, sensitivities: &[(f64, DVector<f64>)], options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>> {
+        options.render(&TrajectoryFigure {
+            name: "channels_states_sensitivity",
+            title: ("Чувствительность вероятностей состояний", "State Probability Sensitivity"),
+            y_label: ("dp/dθ", "dp/dθ"),
+            labels: self.state_labels().iter().map(|name| format!("d{}", name)).collect(),
+            states: sensitivities,
+            band: None,
+            stationary: None,
+            log_scale: false,
+        })
+    }
+}
+
+/// Граф состояний процесса гибели и размножения для `QueuingSystem::plot_state_graph`.
+struct StateGraphFigure<'a> {
+    system: &'a QueuingSystem,
+    graph: StateGraph, // Переходы из матрицы уравнений Колмогорова
+}
+
+impl StateGraphFigure<'_> {
+    /// Подпись дуги из S_from в S_to или `None`, если такого перехода нет.
+    fn transition_label(&self, from: usize, to: usize) -> Option<String> {
+        self.graph.transitions.iter()
+            .find(|t| t.from == from && t.to == to)
+            .map(|t| t.label())
+    }
+}
+
+impl Figure for StateGraphFigure<'_> {
+    fn default_name(&self) -> &str {
+        "queuing_system_states"
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let root_area = root.titled(&options.title_or(self.default_name(), "Граф состояний", "State Graph"), ("sans-serif", 30).into_font())?;
+        let dimensions = root_area.dim_in_pixel();
+
+        let states = self.system.initial_state.len() as i32;
         let step_x = dimensions.0 as f32 / states as f32;
         let step_y = dimensions.1 as f32 / 2.0;
         let rect_height = 40.0;
@@ -64,16 +575,17 @@ impl QueuingSystem {
             // Сначала рисуем контур прямоугольника
             root_area.draw(&Rectangle::new(
                 [(x as i32, (step_y - rect_height / 2.0) as i32), ((x + rect_width) as i32, (step_y + rect_height / 2.0) as i32)],
-                *&BLACK.mix(1.0).stroke_width(2),
+                BLACK.mix(1.0).stroke_width(2),
             ))?;
             root_area.draw(&Rectangle::new(
                 [(x as i32 + 1, (step_y - rect_height / 2.0) as i32 + 1), ((x + rect_width) as i32 - 1, (step_y + rect_height / 2.0) as i32 - 1)],
-                *&WHITE.mix(1.0).filled(),
+                WHITE.mix(1.0).filled(),
             ))?;
 
 
             // Рисуем текст
             let text_style = TextStyle::from(("sans-serif", 20).into_font()).color(&BLACK);
+            let label_style = TextStyle::from(("sans-serif", 16).into_font()).color(&BLACK);
             root_area.draw_text(
                 &state_label,
                 &text_style,
@@ -81,7 +593,7 @@ impl QueuingSystem {
             )?;
 
             // Рисуем адаптивные стрелки
-            if i < states as usize - 1 {
+            if let Some(label) = self.transition_label(i, i + 1) {
                 let next_rect_start_x = (i + 1) as f32 * step_x + offset;
                 let dynamic_arrow_length = (next_rect_start_x - (x + rect_width)).max(30.0);
                 let arrow_start_x = x as i32 + rect_width as i32;
@@ -91,197 +603,184 @@ impl QueuingSystem {
                 // Синяя стрелка
                 root_area.draw(&PathElement::new(
                     vec![(arrow_start_x, step_y as i32 - rect_height as i32 / 4), (arrow_end_x, step_y as i32 - rect_height as i32 / 4)],
-                    *&BLUE.stroke_width(2),
+                    BLUE.stroke_width(2),
                 ))?;
                 root_area.draw(&Polygon::new(
                     vec![(arrow_end_x, step_y as i32 - rect_height as i32 / 4 - arrow_height / 2), (arrow_end_x, step_y as i32 - rect_height as i32 / 4 + arrow_height / 2), (arrow_end_x + arrow_height, step_y as i32 - rect_height as i32 / 4)],
-                    *&BLUE.filled(),
+                    BLUE.filled(),
                 ))?;
 
+                let (label_width, _) = root_area.estimate_text_size(&label, &label_style)?;
                 root_area.draw_text(
-                    &format!("λ = {}", self.lambda_rate),
-                    &text_style.color(&BLUE),
-                    (mid_arrow_x - 20, (step_y - rect_height / 2.0 - 40.0) as i32), // Смещение текста на 50 пикселей вверх от середины стрелки
+                    &label,
+                    &label_style.color(&BLUE),
+                    (mid_arrow_x - label_width as i32 / 2, (step_y - rect_height / 2.0 - 40.0) as i32), // Смещение текста на 50 пикселей вверх от середины стрелки
                 )?;
             }
 
-            if i > 0 {
+            if let Some(label) = i.checked_sub(1).and_then(|previous| self.transition_label(i, previous)) {
                 let previous_rect_end_x = (i - 1) as f32 * step_x + rect_width + offset;
                 let dynamic_arrow_length = (x - previous_rect_end_x).max(30.0);
                 let arrow_start_x = x as i32;
                 let arrow_end_x = arrow_start_x - dynamic_arrow_length as i32 + arrow_height;
 
                 let mid_arrow_x = arrow_end_x + dynamic_arrow_length as i32 / 2;
-                let mu_rate_value = i * self.mu_rate as usize;
 
                 // Красная стрелка
                 root_area.draw(&PathElement::new(
                     vec![(arrow_start_x, step_y as i32 + rect_height as i32 / 4), (arrow_end_x, step_y as i32 + rect_height as i32 / 4)],
-                    *&RED.stroke_width(2),
+                    RED.stroke_width(2),
                 ))?;
 
                 root_area.draw(&Polygon::new(
                     vec![(arrow_end_x, step_y as i32 + rect_height as i32 / 4 - arrow_height / 2), (arrow_end_x, step_y as i32 + rect_height as i32 / 4 + arrow_height / 2), (arrow_end_x - arrow_height, step_y as i32 + rect_height as i32 / 4)],
-                    *&RED.filled(),
+                    RED.filled(),
                 ))?;
 
+                let (label_width, _) = root_area.estimate_text_size(&label, &label_style)?;
                 root_area.draw_text(
-                    &format!("μ = {:.1}", mu_rate_value),
-                    &text_style.color(&RED),
-                    (mid_arrow_x - 20, (step_y + rect_height / 2.0 + 20.0) as i32), // Смещение текста на 30 пикселей вниз от середины стрелки
+                    &label,
+                    &label_style.color(&RED),
+                    (mid_arrow_x - label_width as i32 / 2, (step_y + rect_height / 2.0 + 20.0) as i32), // Смещение текста на 30 пикселей вниз от середины стрелки
                 )?;
             }
         }
 
-        root_area.present()?;
+        // Легенда с параметрами, через которые записаны подписи дуг
+        let mut legend = format!(
+            "λ = {}, μ = {}, s = {}, n = {}",
+            format_rate(self.system.lambda_rate), format_rate(self.system.mu_rate),
+            self.system.num_channels, self.system.queue_size
+        );
+        if self.system.abandonment_rate > 0.0 {
+            legend += &format!(", θ = {}", format_rate(self.system.abandonment_rate));
+        }
+        let legend_style = TextStyle::from(("sans-serif", 20).into_font()).color(&BLACK);
+        let (legend_width, legend_height) = root_area.estimate_text_size(&legend, &legend_style)?;
+        let legend_origin = (20, dimensions.1 as i32 - legend_height as i32 - 30);
+        root_area.draw(&Rectangle::new(
+            [(legend_origin.0 - 10, legend_origin.1 - 10), (legend_origin.0 + legend_width as i32 + 10, legend_origin.1 + legend_height as i32 + 10)],
+            BLACK.stroke_width(1),
+        ))?;
+        root_area.draw_text(&legend, &legend_style, legend_origin)?;
 
         Ok(())
     }
+}
 
-    pub fn generate_kolmogorov_matrix(&self) -> Vec<Vec<i32>> {
-        let lambda_rate = self.lambda_rate;
-        let mu_rate = self.mu_rate;
-        let num_channels_i32 = self.num_channels;
-        let num_channels_usize = self.num_channels as usize;
-        let queue_size = self.queue_size as usize;
-        let queue_max_index = num_channels_usize + queue_size;
-        let number_of_states = queue_max_index + 1;
-
-        (0..number_of_states).map(|i| {
-            (0..number_of_states).map(|j| {
-                match i.cmp(&j) {
-                    Equal => match i {
-                        0 => - lambda_rate,
-                        _ if i < num_channels_usize => - (lambda_rate + i as i32 * mu_rate),
-                        _ => - (lambda_rate + num_channels_i32 * mu_rate),
-                    },
-                    Less => match j {
-                        j if j == i + 1 => if i < num_channels_usize { (i as i32 + 1) * mu_rate } else { num_channels_i32 * mu_rate },
-                        _ => 0,
-                    },
-                    Greater => if j == i - 1 { lambda_rate } else { 0 },
-                }
-            }).collect()
-        }).collect()
-    }
-
-    fn initial_state_to_dvector(initial_state: Arc<Vec<(&'static str, i32)>>) -> DVector<f64> {
-        let values: Vec<f64> = initial_state
-            .iter()
-            .map(|(_key, value)| *value as f64)
-            .collect();
-
-        DVector::from_vec(values)
-    }
-
-    // Функция для преобразования Vec<Vec<i32>> в DMatrix<f64>
-    fn kolmogorov_matrix_to_dmatrix(matrix: Vec<Vec<i32>>) -> DMatrix<f64> {
-        let rows = matrix.len();
-        let cols = matrix.first().map_or(0, Vec::len);
-
-        let flat_matrix: Vec<f64> = matrix.into_iter()
-            .flatten()
-            .map(|val| val as f64)
-            .collect();
-
-        DMatrix::from_row_slice(rows, cols, &flat_matrix)
-    }
-
-    // Функция f(t, x), возвращающая производную состояния
-    fn f(_t: f64, state: &DVector<f64>, matrix: &DMatrix<f64>) -> DVector<f64> {
-        matrix * state
-    }
-
-    // Метод Рунге-Кутты 4-го порядка для одного шага
-    fn runge_kutta4_step(&self, state: &DVector<f64>, matrix: &DMatrix<f64>, t: f64, dt: f64) -> DVector<f64> {
-        let k1 = Self::f(t, state, matrix);
-        let k2 = Self::f(t + dt / 2.0, &(state + &k1 * (dt / 2.0)), matrix);
-        let k3 = Self::f(t + dt / 2.0, &(state + &k2 * (dt / 2.0)), matrix);
-        let k4 = Self::f(t + dt, &(state + &k3 * dt), matrix);
-
-        let new_state = state + &k1 * (dt / 6.0) + &k2 * (dt / 3.0) + &k3 * (dt / 3.0) + &k4 * (dt / 6.0);
-
-        // Нормализация нового состояния
-        let sum: f64 = new_state.iter().sum();
-        new_state / sum
-    }
-
+/// Траектории вероятностей состояний (или их производных) с необязательной полосой квантилей
+/// и отметками стационарных вероятностей.
+struct TrajectoryFigure<'a> {
+    name: &'static str,
+    title: (&'static str, &'static str),   // Заголовок по умолчанию на русском и английском
+    y_label: (&'static str, &'static str), // Подпись оси ординат по умолчанию на русском и английском
+    labels: Vec<String>,                   // Подписи состояний в легенде
+    states: &'a [(f64, DVector<f64>)],     // Пары (t, p(t)) точек траектории
+    band: Option<StateBand<'a>>,
+    stationary: Option<DVector<f64>>,      // Стационарные вероятности, отмечаемые горизонтальными линиями
+    log_scale: bool,                       // Логарифмическая шкала оси ординат
+}
 
-    // Интегрирование системы уравнений
-    pub fn integrate_system(&self) -> Vec<DVector<f64>> {
-        let matrix = Self::kolmogorov_matrix_to_dmatrix(self.generate_kolmogorov_matrix());
-        let initial_state_vec = Self::initial_state_to_dvector(Arc::clone(&QUEUING_SYSTEM_CONFIG.initial_state));
-        let delta_t = QUEUING_SYSTEM_CONFIG.step_size;
+/// Нижняя граница логарифмической шкалы: меньшие (в том числе нулевые) вероятности рисуются на ней.
+const LOG_SCALE_FLOOR: f64 = 1e-6;
 
-        std::iter::successors(Some((initial_state_vec, 0.0)), |(last_state, t)| {
-            Some((self.runge_kutta4_step(last_state, &matrix, *t, delta_t), t + delta_t))
-        })
-            .take((QUEUING_SYSTEM_CONFIG.num_iterations + 1) as usize)
-            .map(|(state, _)| state)
-            .collect()
+impl TrajectoryFigure<'_> {
+    /// Первый и последний моменты траектории.
+    fn time_range(&self) -> (f64, f64) {
+        (self.states.first().map_or(0.0, |(t, _)| *t), self.states.last().map_or(1.0, |(t, _)| *t))
     }
 
+    fn draw_series<'a, DB: DrawingBackend + 'a, Y>(&self, mut chart: ChartContext<'a, DB, Cartesian2d<RangedCoordf64, Y>>, options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+        Y: Ranged<ValueType = f64> + ValueFormatter<f64>,
+    {
+        let floor = |p: f64| if self.log_scale { p.max(LOG_SCALE_FLOOR) } else { p };
+        let num_states = self.states.first().map_or(0, |(_, v)| v.len());
+        let colors = palette(num_states);
+        let (t_start, t_end) = self.time_range();
+
+        let log_formatter = |y: &f64| format!("{:.0e}", y);
+        let mut mesh = chart.configure_mesh();
+        mesh.x_desc(options.x_label_or(self.default_name(), "Время", "Time"))
+            .y_desc(options.y_label_or(self.default_name(), self.y_label.0, self.y_label.1));
+        if self.log_scale {
+            mesh.y_label_formatter(&log_formatter);
+        }
+        mesh.draw()?;
+
+        // Полоса квантилей: многоугольник по верхней границе вперёд и по нижней назад
+        if let Some((lower, upper)) = self.band {
+            for (i, color) in colors.iter().enumerate() {
+                let outline: Vec<(f64, f64)> = upper.iter().map(|(t, state)| (*t, floor(state[i])))
+                    .chain(lower.iter().rev().map(|(t, state)| (*t, floor(state[i]))))
+                    .collect();
+                chart.draw_series(std::iter::once(Polygon::new(outline, color.mix(0.2).filled())))?;
+            }
+        }
 
-    pub fn plot_states(&self, states: Vec<DVector<f64>>) -> Result<(), Box<dyn std::error::Error>> {
-        let root_area = BitMapBackend::new("channels_states.png", (1024, 768)).into_drawing_area();
-        root_area.fill(&WHITE)?;
-
-        let num_states = states.first().map_or(0, |v| v.len());
-        let num_steps = states.len();
-
-        let max_y = states.iter().flatten().cloned().fold(f64::NEG_INFINITY, f64::max);
-        let min_y = states.iter().flatten().cloned().fold(f64::INFINITY, f64::min);
-
-        let mut chart = ChartBuilder::on(&root_area)
-            .caption("System States Over Time", ("sans-serif", 50).into_font())
-            .margin(10)
-            .x_label_area_size(30)
-            .y_label_area_size(30)
-            .build_cartesian_2d(0..num_steps, min_y..max_y)?;
-
-        chart.configure_mesh().draw()?;
-
-        let colors = [
-            &RED, &GREEN, &BLUE, &YELLOW, &CYAN, &MAGENTA, &BLACK,
-            // Дополнительные цвета, если у вас больше состояний
-        ];
-
-        let mut series = Vec::new();
-
-        for i in 0..num_states {
-            let line_series = LineSeries::new(
-                states.iter().enumerate().map(|(step, state)| (step, state[i])),
-                colors[i % colors.len()],
-            );
-            series.push((line_series, format!("z_{}", i + 1), colors[i % colors.len()]));
+        if let Some(stationary) = &self.stationary {
+            for (i, color) in colors.iter().enumerate() {
+                chart.draw_series(DashedLineSeries::new(
+                    [(t_start, floor(stationary[i])), (t_end, floor(stationary[i]))],
+                    6,
+                    4,
+                    color.stroke_width(1),
+                ))?;
+            }
         }
 
-        for (line_series, label, &color) in series {
+        for (i, (label, &color)) in self.labels.iter().zip(colors.iter()).enumerate() {
             chart
-                .draw_series(line_series)
-                .expect("Failed to draw line series")
-                .label(label)
-                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+                .draw_series(LineSeries::new(
+                    self.states.iter().map(|(t, state)| (*t, floor(state[i]))),
+                    color.stroke_width(2),
+                ))?
+                .label(label.as_str())
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
         }
 
         chart
             .configure_series_labels()
-            .background_style(&WHITE.mix(0.8))
-            .border_style(&BLACK)
+            .position(options.legend_position.to_series_label_position())
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
             .draw()?;
 
-        root_area.present()?;
         Ok(())
     }
-
 }
 
+impl Figure for TrajectoryFigure<'_> {
+    fn default_name(&self) -> &str {
+        self.name
+    }
 
-
-
-
-
-
-
-
-
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn std::error::Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+
+        let (lower, upper) = self.band.unwrap_or((self.states, self.states));
+        let stationary = self.stationary.iter().flat_map(|p| p.iter().cloned());
+        let max_y = upper.iter().flat_map(|(_, p)| p.iter().cloned()).chain(stationary.clone()).fold(f64::NEG_INFINITY, f64::max);
+        let min_y = lower.iter().flat_map(|(_, p)| p.iter().cloned()).chain(stationary).fold(f64::INFINITY, f64::min);
+        let (t_start, t_end) = self.time_range();
+
+        let mut builder = ChartBuilder::on(root);
+        builder
+            .caption(options.title_or(self.default_name(), self.title.0, self.title.1), ("sans-serif", 50).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60);
+
+        if self.log_scale {
+            let chart = builder.build_cartesian_2d(t_start..t_end, (min_y.max(LOG_SCALE_FLOOR)..max_y.max(LOG_SCALE_FLOOR)).log_scale())?;
+            self.draw_series(chart, options)
+        } else {
+            let chart = builder.build_cartesian_2d(t_start..t_end, min_y..max_y)?;
+            self.draw_series(chart, options)
+        }
+    }
+}
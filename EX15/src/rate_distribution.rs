@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Распределение неопределённой интенсивности λ или μ.
+#[derive(Debug, Clone)]
+pub enum RateDistribution {
+    Fixed(f64),                              // Интенсивность известна точно
+    Uniform { lower: f64, upper: f64 },      // Известен только диапазон значений
+    Normal { mean: f64, std_dev: f64 },      // Нормальное распределение, усечённое нулём
+    LogNormal { median: f64, sigma: f64 },   // ln(rate) ~ N(ln(median), sigma²)
+    Bootstrap(Vec<f64>),                     // Выборка длительностей, по которой оценивается интенсивность
+}
+
+impl RateDistribution {
+    /// Проверяет, что распределение даёт только положительные интенсивности.
+    /// Для усечённого нормального распределения требуется положительное среднее:
+    /// тогда значение отбрасывается с вероятностью не больше 1/2 и генерация завершается.
+    /// Для бутстрепа достаточно одной положительной длительности: перевыборка с нулевой
+    /// суммой повторяется, а вероятность её получить меньше 1.
+    /// # Возвращаемое значение
+    /// Ошибка с описанием некорректного параметра, тип: `Result<(), Box<dyn Error>>`.
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        let valid = match self {
+            RateDistribution::Fixed(rate) => *rate > 0.0,
+            RateDistribution::Uniform { lower, upper } => *lower > 0.0 && lower <= upper,
+            RateDistribution::Normal { mean, std_dev } => *mean > 0.0 && *std_dev >= 0.0,
+            RateDistribution::LogNormal { median, sigma } => *median > 0.0 && *sigma >= 0.0,
+            RateDistribution::Bootstrap(durations) => durations.iter().all(|x| x.is_finite() && *x >= 0.0) && durations.iter().sum::<f64>() > 0.0,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("распределение интенсивности задано некорректно: {:?}", self).into())
+        }
+    }
+
+    /// Генерирует значение интенсивности.
+    /// Для бутстрепа выборка длительностей перевыбирается с возвращением,
+    /// интенсивность оценивается как n / Σx; перевыборка из одних нулевых длительностей
+    /// повторяется. Распределение должно пройти проверку `validate`.
+    /// # Параметры
+    /// * `rng` - Генератор случайных чисел.
+    /// # Возвращаемое значение
+    /// Положительное значение интенсивности, тип: `f64`.
+    pub fn sample(&self, rng: &mut StdRng) -> f64 {
+        match self {
+            RateDistribution::Fixed(rate) => *rate,
+            RateDistribution::Uniform { lower, upper } => lower + (upper - lower) * rng.gen::<f64>(),
+            RateDistribution::Normal { mean, std_dev } => loop {
+                let rate = mean + std_dev * standard_normal(rng);
+                if rate > 0.0 {
+                    break rate;
+                }
+            },
+            RateDistribution::LogNormal { median, sigma } => (median.ln() + sigma * standard_normal(rng)).exp(),
+            RateDistribution::Bootstrap(durations) => loop {
+                let total: f64 = (0..durations.len())
+                    .map(|_| durations[rng.gen_range(0..durations.len())])
+                    .sum();
+                if total > 0.0 {
+                    break durations.len() as f64 / total;
+                }
+            },
+        }
+    }
+}
+
+/// Генерирует стандартную нормальную величину преобразованием Бокса–Мюллера.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = 1.0 - rng.gen::<f64>();
+    let u2: f64 = rng.gen::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn bootstrap_redraws_resamples_of_zero_durations() {
+        // Перевыборка из (0, 0, 1) состоит из одних нулей с вероятностью 8/27
+        let distribution = RateDistribution::Bootstrap(vec![0.0, 0.0, 1.0]);
+        distribution.validate().unwrap();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..1_000 {
+            let rate = distribution.sample(&mut rng);
+            // Σx принимает значения 1, 2 или 3
+            assert!([3.0, 1.5, 1.0].contains(&rate), "{}", rate);
+        }
+    }
+
+    #[test]
+    fn rejects_bootstrap_without_positive_durations() {
+        assert!(RateDistribution::Bootstrap(vec![]).validate().is_err());
+        assert!(RateDistribution::Bootstrap(vec![0.0, 0.0]).validate().is_err());
+        assert!(RateDistribution::Bootstrap(vec![1.0, -0.5]).validate().is_err());
+    }
+}
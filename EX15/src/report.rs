@@ -0,0 +1,240 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::config::Config;
+use crate::estimation::ParameterEstimates;
+use crate::queuing_system::QueuingSystem;
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+use crate::state_graph::format_rate;
+
+/// Характеристика СМО с интервалом, полученным из неопределённости параметров.
+#[derive(Debug, Clone)]
+pub struct CharacteristicInterval {
+    pub name: &'static str,
+    pub value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+/// Собирает скалярные характеристики СМО в таблицу.
+/// # Параметры
+/// * `system` - Любая модель, реализующая `QueuingSystemCharacteristics`.
+/// # Возвращаемое значение
+/// Пары (название характеристики, значение), тип: `Vec<(&'static str, f64)>`.
+pub fn characteristics_report<T: QueuingSystemCharacteristics>(system: &T) -> Vec<(&'static str, f64)> {
+    vec![
+        ("Коэффициент загрузки СМО", system.calculate_load_factor()),
+        ("Вероятность простоя системы", system.calculate_probability_of_downtime()),
+        ("Вероятность отказа", system.calculate_rejection_probability()),
+        ("Среднее число заявок, поступающих за время T", system.calculate_average_incoming_requests_during_t()),
+        ("Среднее время обслуживания заявки", system.calculate_average_service_time_per_request()),
+        ("Среднее время обслуживания одним каналом заявок за время T", system.average_service_time_per_channel_for_t()),
+        ("Среднее число занятых каналов", system.calculate_average_busy_channels()),
+        ("Среднее число заявок в очереди", system.calculate_average_number_of_requests_in_queue()),
+        ("Среднее время пребывания заявки в очереди", system.calculate_average_waiting_time_in_queue()),
+        ("Общее количество заявок в системе", system.calculate_total_number_of_requests()),
+        ("Среднее время ожидания заявки в системе", system.calculate_average_waiting_time()),
+        ("Среднее время пребывания заявки в системе", system.calculate_average_time_in_system()),
+    ]
+}
+
+/// Название характеристики из `characteristics_report` на английском языке для подписей графиков.
+/// # Параметры
+/// * `name` - Название характеристики.
+/// # Возвращаемое значение
+/// Английское название либо исходное, если перевода нет, тип: `&str`.
+pub fn english_characteristic_name(name: &str) -> &str {
+    match name {
+        "Коэффициент загрузки СМО" => "Load factor",
+        "Вероятность простоя системы" => "Idle probability",
+        "Вероятность отказа" => "Rejection probability",
+        "Среднее число заявок, поступающих за время T" => "Mean arrivals during T",
+        "Среднее время обслуживания заявки" => "Mean service time",
+        "Среднее время обслуживания одним каналом заявок за время T" => "Service time per channel during T",
+        "Среднее число занятых каналов" => "Mean busy channels",
+        "Среднее число заявок в очереди" => "Mean queue length",
+        "Среднее время пребывания заявки в очереди" => "Mean waiting time in queue",
+        "Общее количество заявок в системе" => "Mean number in system",
+        "Среднее время ожидания заявки в системе" => "Mean waiting time",
+        "Среднее время пребывания заявки в системе" => "Mean time in system",
+        other => other,
+    }
+}
+
+/// Собирает характеристики модели `QueuingSystem` по стационарному распределению
+/// (`stationary_characteristics`): в отличие от формул M/M/s/∞ оно учитывает ограничение очереди
+/// n местами, уходы из очереди и отказ от присоединения. Строки описаны `characteristic_descriptions`.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// # Возвращаемое значение
+/// Пары (название характеристики, значение), тип: `Result<Vec<(&'static str, f64)>, Box<dyn Error>>`.
+pub fn queuing_system_report(system: &QueuingSystem) -> Result<Vec<(&'static str, f64)>, Box<dyn Error>> {
+    Ok(characteristics_report(&system.stationary_characteristics()?))
+}
+
+/// Параметр модели для таблицы исходных данных отчёта.
+#[derive(Debug, Clone)]
+pub struct ModelParameter {
+    pub name: &'static str,
+    pub symbol: &'static str,
+    pub value: String,
+    pub unit: &'static str,
+}
+
+/// Собирает параметры модели для отчётов. Время измеряется в часах,
+/// интенсивности — в заявках в час, как в условии задачи.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// # Возвращаемое значение
+/// Параметры модели, тип: `Vec<ModelParameter>`.
+pub fn model_parameters(system: &QueuingSystem) -> Vec<ModelParameter> {
+    [
+        ("Интенсивность потока заявок", "λ", format_rate(system.lambda_rate), "1/ч"),
+        ("Интенсивность обслуживания одним каналом", "μ", format_rate(system.mu_rate), "1/ч"),
+        ("Интенсивность ухода из очереди", "θ", format_rate(system.abandonment_rate), "1/ч"),
+        ("Число каналов", "s", system.num_channels.to_string(), ""),
+        ("Число мест в очереди", "n", system.queue_size.to_string(), ""),
+        ("Период наблюдения", "T", system.time.to_string(), "ч"),
+        ("Шаг интегрирования", "h", format_rate(system.step_size), "ч"),
+        ("Число шагов интегрирования", "N", system.num_iterations.to_string(), ""),
+    ]
+        .into_iter()
+        .map(|(name, symbol, value, unit)| ModelParameter { name, symbol, value, unit })
+        .collect()
+}
+
+/// Формула, единица измерения и смысл характеристики из `characteristics_report` для читателей отчёта.
+#[derive(Debug, Clone, Copy)]
+pub struct CharacteristicDescription {
+    pub formula: &'static str,        // Формула по стационарному распределению (`stationary_characteristics`)
+    pub latex: &'static str,          // Та же формула в записи LaTeX
+    pub unit: &'static str,           // Единица измерения; пустая для безразмерных величин
+    pub interpretation: &'static str, // Что означает значение характеристики
+}
+
+/// Описания характеристик в порядке строк `characteristics_report`, вычисленных
+/// `QueuingSystem::stationary_characteristics`. P_i — стационарные вероятности состояний S_0..S_{s+n}
+/// (A·P = 0, Σ P_i = 1), X = Σ min(i, s)·μ·P_i — пропускная способность, λ_пр = X + θ·L_q —
+/// интенсивность потока принятых заявок (обслуженных и ушедших из очереди), λ — интенсивность
+/// потока заявок (при групповых поступлениях λ·E[размер группы]), T — период наблюдения.
+/// Формулы верны для конечной очереди из n мест, в том числе при уходах из очереди и отказе от присоединения.
+/// # Возвращаемое значение
+/// Описания характеристик, тип: `Vec<CharacteristicDescription>`.
+pub fn characteristic_descriptions() -> Vec<CharacteristicDescription> {
+    [
+        ("ρ = λ / μ", r"\rho = \lambda / \mu", "",
+            "Сколько каналов в среднем нужно, чтобы успевать обслуживать поток заявок"),
+        ("P₀: A·P = 0, Σᵢ P_i = 1", r"P_0:\; A P = 0,\; \sum_i P_i = 1", "",
+            "Доля времени, когда в системе нет ни одной заявки и все каналы свободны"),
+        ("P_отк = 1 − X / λ, X = Σ min(i, s) · μ · P_i",
+            r"P_{\text{отк}} = 1 - X / \lambda,\; X = \sum_i \min(i, s) \mu P_i", "",
+            "Доля поступающих заявок, которые не будут обслужены: отказ при заполненной очереди, отказ от присоединения или уход из очереди"),
+        ("N = λ · T", r"N = \lambda T", "заявок",
+            "Сколько заявок в среднем поступит за период наблюдения"),
+        ("t_обс = 1 / μ", r"t_{\text{обс}} = 1 / \mu", "ч",
+            "Сколько в среднем длится обслуживание одной заявки"),
+        ("ρ · T", r"\rho T", "ч",
+            "Суммарное время работы каналов, которого требуют заявки, поступившие за период наблюдения"),
+        ("k̄ = Σ min(i, s) · P_i", r"\bar{k} = \sum_i \min(i, s) P_i", "каналов",
+            "Сколько каналов в среднем заняты обслуживанием"),
+        ("L_q = Σᵢ₌ₛ₊₁ˢ⁺ⁿ (i − s) · P_i", r"L_q = \sum_{i=s+1}^{s+n} (i - s) P_i", "заявок",
+            "Сколько заявок в среднем ожидает в очереди"),
+        ("W_q = L_q / λ_пр, λ_пр = X + θ · L_q",
+            r"W_q = L_q / \lambda_{\text{пр}},\; \lambda_{\text{пр}} = X + \theta L_q", "ч",
+            "Сколько в среднем заявка ждёт начала обслуживания"),
+        ("L = Σ i · P_i", r"L = \sum_i i P_i", "заявок",
+            "Сколько заявок в среднем находится в системе: в очереди и на обслуживании"),
+        ("W = L_q / λ_пр", r"W = L_q / \lambda_{\text{пр}}", "ч",
+            "Сколько в среднем заявка ожидает в системе до начала обслуживания"),
+        ("T_сист = L / λ_пр", r"T_{\text{сист}} = L / \lambda_{\text{пр}}", "ч",
+            "Сколько в среднем заявка проводит в системе от поступления до ухода"),
+    ]
+        .into_iter()
+        .map(|(formula, latex, unit, interpretation)| CharacteristicDescription { formula, latex, unit, interpretation })
+        .collect()
+}
+
+/// Собирает характеристики СМО с интервалами, перенесёнными из доверительных интервалов λ и μ.
+/// Характеристики вычисляются во всех сочетаниях границ и точечных оценок λ и μ,
+/// в качестве интервала берутся минимум и максимум полученных значений.
+/// # Параметры
+/// * `estimates` - Оценки λ и μ с доверительными интервалами.
+/// * `base` - Конфигурация, задающая число каналов, размер очереди и время T.
+/// # Возвращаемое значение
+/// Характеристики с интервалами, тип: `Result<Vec<CharacteristicInterval>, Box<dyn Error>>`.
+pub fn characteristics_report_with_intervals(estimates: &ParameterEstimates, base: &Config) -> Result<Vec<CharacteristicInterval>, Box<dyn Error>> {
+    let system_for = |lambda_rate: f64, mu_rate: f64| QueuingSystem::new(
+        lambda_rate,
+        mu_rate,
+        base.abandonment_rate,
+        base.balking,
+        base.batch_arrivals.clone(),
+        base.num_channels,
+        base.queue_size,
+        Arc::clone(&base.initial_state),
+        base.time,
+        base.num_iterations,
+        base.step_size
+    );
+
+    let lambdas = [estimates.lambda.lower, estimates.lambda.rate, estimates.lambda.upper];
+    let mus = [estimates.mu.lower, estimates.mu.rate, estimates.mu.upper];
+    let corner_reports: Vec<Vec<(&'static str, f64)>> = lambdas.iter()
+        .flat_map(|&lambda_rate| mus.iter().map(move |&mu_rate| (lambda_rate, mu_rate)))
+        .map(|(lambda_rate, mu_rate)| queuing_system_report(&system_for(lambda_rate, mu_rate)))
+        .collect::<Result<_, _>>()?;
+
+    Ok(queuing_system_report(&system_for(estimates.lambda.rate, estimates.mu.rate))?
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, value))| {
+            let (lower, upper) = corner_reports.iter()
+                .map(|report| report[index].1)
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+            CharacteristicInterval { name, value, lower, upper }
+        })
+        .collect())
+}
+
+/// Форматирует отчёты нескольких моделей в таблицу для сравнения.
+/// Отчёты должны быть построены `characteristics_report`, чтобы строки совпадали.
+/// # Параметры
+/// * `columns` - Пары (название модели, отчёт).
+/// # Возвращаемое значение
+/// Текстовая таблица, тип: `String`.
+pub fn format_comparison_table(columns: &[(&str, Vec<(&'static str, f64)>)]) -> String {
+    let names: Vec<&'static str> = columns.first()
+        .map_or_else(Vec::new, |(_, report)| report.iter().map(|(name, _)| *name).collect());
+    let name_width = names.iter().map(|name| name.chars().count()).max().unwrap_or(0);
+
+    let header = columns.iter()
+        .fold(format!("{:name_width$}", ""), |line, (model, _)| format!("{} | {:>14}", line, model));
+    let rows = names.iter().enumerate().map(|(row, name)| {
+        columns.iter().fold(format!("{:name_width$}", name), |line, (_, report)| {
+            format!("{} | {:>14.6}", line, report[row].1)
+        })
+    });
+
+    std::iter::once(header).chain(rows).collect::<Vec<String>>().join("\n")
+}
+
+/// Сохраняет отчёты нескольких моделей в CSV-файл: строка на характеристику, столбец на модель.
+/// # Параметры
+/// * `path` - Путь к CSV-файлу.
+/// * `columns` - Пары (название модели, отчёт).
+/// # Возвращаемое значение
+/// Результат записи файла, тип: `Result<(), Box<dyn Error>>`.
+pub fn export_comparison_csv<P: AsRef<Path>>(path: P, columns: &[(&str, Vec<(&'static str, f64)>)]) -> Result<(), Box<dyn Error>> {
+    let header = columns.iter()
+        .fold("characteristic".to_string(), |line, (model, _)| format!("{},{}", line, model));
+    let rows = columns.first().map_or_else(Vec::new, |(_, report)| {
+        report.iter().enumerate().map(|(row, (name, _))| {
+            columns.iter().fold(format!("\"{}\"", name), |line, (_, report)| format!("{},{}", line, report[row].1))
+        }).collect()
+    });
+
+    fs::write(path, std::iter::once(header).chain(rows).collect::<Vec<String>>().join("\n") + "\n")?;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use std::error::Error;
+use nalgebra::{DMatrix, DVector};
+
+use crate::markov_chain;
+use crate::stationary_characteristics::StationaryCharacteristics;
+
+/// Состояние СМО с повторными вызовами: число заявок в системе и число заявок на орбите.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetrialState {
+    pub customers: i32, // Число заявок на обслуживании и в очереди
+    pub orbit: i32,     // Число заявок на орбите, ожидающих повторного вызова
+}
+
+/// СМО M/M/s/n с орбитой повторных вызовов.
+/// Заявка, заставшая систему заполненной, не теряется, а уходит на орбиту
+/// и повторяет вызов с интенсивностью θ. Неудачный повторный вызов заканчивается
+/// возвратом на орбиту с вероятностью настойчивости p или окончательным уходом
+/// с вероятностью 1 - p. Орбита усечена до `orbit_size` заявок: заявка, заставшая
+/// заполненными и систему, и орбиту, теряется.
+pub struct RetrialQueuingSystem {
+    pub lambda_rate: f64,  // Интенсивность потока первичных заявок
+    pub mu_rate: f64,      // Интенсивность обработки одним каналом
+    pub retrial_rate: f64, // Интенсивность повторных вызовов одной заявки с орбиты
+    pub persistence: f64,  // Вероятность вернуться на орбиту после неудачного повторного вызова
+    pub num_channels: i32, // Количество каналов
+    pub queue_size: i32,   // Ограничение на размер очереди
+    pub orbit_size: i32,   // Граница усечения орбиты
+    pub time: i32,         // Время
+}
+
+impl RetrialQueuingSystem {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               retrial_rate: f64,
+               persistence: f64,
+               num_channels: i32,
+               queue_size: i32,
+               orbit_size: i32,
+               time: i32
+    ) -> RetrialQueuingSystem {
+
+        RetrialQueuingSystem {
+            lambda_rate,
+            mu_rate,
+            retrial_rate,
+            persistence,
+            num_channels,
+            queue_size,
+            orbit_size,
+            time
+        }
+    }
+
+    fn capacity(&self) -> i32 {
+        self.num_channels + self.queue_size
+    }
+
+    /// Перечисляет состояния (k, j), k = 0..s+n, j = 0..orbit_size.
+    /// # Возвращаемое значение
+    /// Состояния в порядке нумерации строк матрицы Колмогорова, тип: `Vec<RetrialState>`.
+    pub fn states(&self) -> Vec<RetrialState> {
+        (0..=self.orbit_size)
+            .flat_map(|orbit| (0..=self.capacity()).map(move |customers| RetrialState { customers, orbit }))
+            .collect()
+    }
+
+    /// Перечисляет переходы из состояния.
+    /// # Возвращаемое значение
+    /// Пары (новое состояние, интенсивность перехода), тип: `Vec<(RetrialState, f64)>`.
+    fn transitions(&self, state: RetrialState) -> Vec<(RetrialState, f64)> {
+        let RetrialState { customers, orbit } = state;
+        let full = customers == self.capacity();
+        let mut transitions = Vec::new();
+
+        // Первичная заявка занимает место в системе или уходит на орбиту
+        if !full {
+            transitions.push((RetrialState { customers: customers + 1, orbit }, self.lambda_rate));
+        } else if orbit < self.orbit_size {
+            transitions.push((RetrialState { customers, orbit: orbit + 1 }, self.lambda_rate));
+        }
+
+        // Повторный вызов: успешный занимает место в системе, неудачный с вероятностью 1 - p уходит
+        if orbit > 0 {
+            let rate = orbit as f64 * self.retrial_rate;
+            if !full {
+                transitions.push((RetrialState { customers: customers + 1, orbit: orbit - 1 }, rate));
+            } else if self.persistence < 1.0 {
+                transitions.push((RetrialState { customers, orbit: orbit - 1 }, rate * (1.0 - self.persistence)));
+            }
+        }
+
+        if customers > 0 {
+            transitions.push((RetrialState { customers: customers - 1, orbit }, customers.min(self.num_channels) as f64 * self.mu_rate));
+        }
+
+        transitions
+    }
+
+    /// Строит матрицу правых частей уравнений Колмогорова на усечённом двумерном пространстве состояний.
+    /// # Возвращаемое значение
+    /// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+    pub fn generate_kolmogorov_matrix(&self) -> DMatrix<f64> {
+        markov_chain::generator_matrix(&self.states(), |state| self.transitions(*state))
+    }
+
+    /// Вычисляет стационарное распределение по состояниям `states()`.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний, тип: `Result<DVector<f64>, Box<dyn Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn Error>> {
+        markov_chain::stationary_distribution(&self.generate_kolmogorov_matrix())
+    }
+
+    /// Сводит распределение по состояниям (k, j) к распределению числа заявок в системе.
+    /// # Параметры
+    /// * `distribution` - Вероятности состояний `states()`.
+    /// # Возвращаемое значение
+    /// Вероятности S_0..S_{s+n}, тип: `DVector<f64>`.
+    pub fn customer_distribution(&self, distribution: &DVector<f64>) -> DVector<f64> {
+        markov_chain::marginal_distribution(&self.states(), distribution, self.capacity() as usize + 1, |state| state.customers as usize)
+    }
+
+    /// Вычисляет среднее число заявок на орбите.
+    /// # Возвращаемое значение
+    /// Σ j·p(k, j), тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_average_orbit_size(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.states().iter()
+            .zip(self.stationary_distribution()?.iter())
+            .map(|(state, p)| state.orbit as f64 * p)
+            .sum())
+    }
+
+    /// Вычисляет суммарную интенсивность вызовов: первичных и повторных.
+    /// # Возвращаемое значение
+    /// λ + θ·E[j], тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_attempt_rate(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.lambda_rate + self.retrial_rate * self.calculate_average_orbit_size()?)
+    }
+
+    /// Вычисляет эффективную интенсивность потока заявок, попавших в систему.
+    /// # Возвращаемое значение
+    /// Σ min(k, s)·μ·p(k, j), тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_effective_arrival_rate(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.states().iter()
+            .zip(self.stationary_distribution()?.iter())
+            .map(|(state, p)| state.customers.min(self.num_channels) as f64 * self.mu_rate * p)
+            .sum())
+    }
+
+    /// Вычисляет вероятность того, что первичная заявка так и не будет обслужена.
+    /// # Возвращаемое значение
+    /// 1 - λ_эфф / λ, тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_eventual_loss_probability(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(1.0 - self.calculate_effective_arrival_rate()? / self.lambda_rate)
+    }
+
+    /// Вычисляет вероятность того, что орбита заполнена до границы усечения.
+    /// Если она не мала, `orbit_size` следует увеличить.
+    /// # Возвращаемое значение
+    /// P(j = orbit_size), тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_truncation_probability(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.states().iter()
+            .zip(self.stationary_distribution()?.iter())
+            .filter(|(state, _)| state.orbit == self.orbit_size)
+            .map(|(_, p)| p)
+            .sum())
+    }
+
+    /// Вычисляет характеристики СМО по числу заявок в системе.
+    /// Отказом считается окончательная потеря первичной заявки.
+    /// # Возвращаемое значение
+    /// Характеристики СМО, тип: `Result<StationaryCharacteristics, Box<dyn Error>>`.
+    pub fn characteristics(&self) -> Result<StationaryCharacteristics, Box<dyn Error>> {
+        Ok(StationaryCharacteristics::new(
+            self.lambda_rate,
+            self.mu_rate,
+            self.num_channels,
+            self.queue_size,
+            self.time,
+            self.customer_distribution(&self.stationary_distribution()?),
+            self.calculate_effective_arrival_rate()?
+        ))
+    }
+}
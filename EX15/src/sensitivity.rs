@@ -0,0 +1,311 @@
+use std::error::Error;
+
+use nalgebra::DVector;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::batch_arrivals::BatchArrivals;
+use crate::config::Config;
+use crate::markov_chain::{self, kolmogorov_matrix_to_dmatrix};
+use crate::plot_options::{Figure, PlotOptions};
+use crate::queuing_system::QueuingSystem;
+use crate::report::{characteristics_report, english_characteristic_name};
+use crate::stationary_characteristics::StationaryCharacteristics;
+
+/// Параметр СМО, по которому вычисляется чувствительность.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensitivityParameter {
+    Lambda,   // Интенсивность потока заявок λ
+    Mu,       // Интенсивность обслуживания одним каналом μ
+    Channels, // Число каналов s (дискретный параметр)
+    Queue,    // Ограничение на длину очереди n (дискретный параметр)
+}
+
+/// Чувствительность одной характеристики СМО к параметрам λ, μ, s и n.
+/// Для λ и μ хранятся производные, для s и n — приращения при увеличении параметра на 1.
+/// Эластичность — относительное изменение характеристики на относительное изменение параметра.
+#[derive(Debug, Clone)]
+pub struct CharacteristicSensitivity {
+    pub name: &'static str,
+    pub value: f64,
+    pub d_lambda: f64,
+    pub d_mu: f64,
+    pub delta_channels: f64,
+    pub delta_queue: f64,
+    pub elasticity_lambda: f64,
+    pub elasticity_mu: f64,
+    pub elasticity_channels: f64,
+    pub elasticity_queue: f64,
+}
+
+impl CharacteristicSensitivity {
+    /// Эластичность характеристики по заданному параметру.
+    /// # Параметры
+    /// * `parameter` - Параметр СМО.
+    /// # Возвращаемое значение
+    /// Эластичность, тип: `f64`.
+    pub fn elasticity(&self, parameter: SensitivityParameter) -> f64 {
+        match parameter {
+            SensitivityParameter::Lambda => self.elasticity_lambda,
+            SensitivityParameter::Mu => self.elasticity_mu,
+            SensitivityParameter::Channels => self.elasticity_channels,
+            SensitivityParameter::Queue => self.elasticity_queue,
+        }
+    }
+}
+
+/// Производная частного u / v.
+fn quotient_derivative(numerator: f64, d_numerator: f64, denominator: f64, d_denominator: f64) -> f64 {
+    (d_numerator * denominator - numerator * d_denominator) / (denominator * denominator)
+}
+
+/// Вычисляет точные производные характеристик `characteristics_report` по λ или μ.
+/// Производная стационарного распределения находится из A·∂p = -(∂A)·p, Σ∂p = 0,
+/// остальные величины дифференцируются по формулам `StationaryCharacteristics`.
+/// # Параметры
+/// * `system` - Модель СМО.
+/// * `characteristics` - Стационарные характеристики модели.
+/// * `parameter` - Параметр дифференцирования.
+/// # Возвращаемое значение
+/// Производные в порядке строк отчёта либо ошибка для дискретных параметров s и n,
+/// тип: `Result<Vec<f64>, Box<dyn Error>>`.
+fn characteristics_derivative(system: &QueuingSystem, characteristics: &StationaryCharacteristics, parameter: SensitivityParameter) -> Result<Vec<f64>, Box<dyn Error>> {
+    let derivative_matrix = system.kolmogorov_matrix_derivative(parameter)
+        .ok_or("производные вычисляются только по непрерывным параметрам λ и μ")?;
+    let derivative_matrix = kolmogorov_matrix_to_dmatrix(derivative_matrix);
+    let matrix = kolmogorov_matrix_to_dmatrix(system.generate_kolmogorov_matrix());
+    let distribution = &characteristics.distribution;
+    let d_distribution = markov_chain::stationary_sensitivity(&matrix, &derivative_matrix, distribution)?;
+
+    // Интенсивность потока заявок λ·E[размер группы] линейна по λ
+    let (d_lambda, d_mu) = match parameter {
+        SensitivityParameter::Lambda => (system.batch_arrivals.as_ref().map_or(1.0, BatchArrivals::mean_size), 0.0),
+        _ => (0.0, 1.0),
+    };
+    let num_channels = system.num_channels as usize;
+    let weighted = |values: &DVector<f64>, weight: &dyn Fn(usize) -> usize| -> f64 {
+        values.iter().enumerate().map(|(i, value)| weight(i) as f64 * value).sum()
+    };
+    let busy = |i: usize| i.min(num_channels);
+    let waiting = |i: usize| i.saturating_sub(num_channels);
+    let customers = |i: usize| i;
+
+    let (lambda, mu, time) = (characteristics.lambda_rate, characteristics.mu_rate, characteristics.time as f64);
+    let throughput = characteristics.throughput;
+    let d_throughput = d_mu * weighted(distribution, &busy) + mu * weighted(&d_distribution, &busy);
+    let (queue_length, d_queue_length) = (weighted(distribution, &waiting), weighted(&d_distribution, &waiting));
+    let (total, d_total) = (weighted(distribution, &customers), weighted(&d_distribution, &customers));
+    // Поток принятых заявок X + θ·Lq: θ не зависит от λ и μ
+    let admitted_rate = characteristics.admitted_rate;
+    let d_admitted_rate = d_throughput + system.abandonment_rate * d_queue_length;
+    let d_load = quotient_derivative(lambda, d_lambda, mu, d_mu);
+    let d_waiting_time = quotient_derivative(queue_length, d_queue_length, admitted_rate, d_admitted_rate);
+
+    Ok(vec![
+        d_load,
+        d_distribution[0],
+        -quotient_derivative(throughput, d_throughput, lambda, d_lambda),
+        d_lambda * time,
+        quotient_derivative(1.0, 0.0, mu, d_mu),
+        d_load * time,
+        weighted(&d_distribution, &busy),
+        d_queue_length,
+        d_waiting_time,
+        d_total,
+        d_waiting_time,
+        quotient_derivative(total, d_total, admitted_rate, d_admitted_rate),
+    ])
+}
+
+/// Относительное изменение характеристики на относительное изменение параметра.
+/// При нулевом значении характеристики эластичность не определена и считается равной 0.
+fn elasticity(derivative: f64, parameter: f64, value: f64) -> f64 {
+    if value == 0.0 { 0.0 } else { derivative * parameter / value }
+}
+
+/// Вычисляет чувствительность стационарных характеристик (`stationary_characteristics`) к λ, μ, s и n.
+/// Производные по λ и μ точные: они находятся через производную матрицы уравнений Колмогорова,
+/// по s и n — конечными разностями при увеличении параметра на 1 (эластичность при этом
+/// считается по дуге: (ΔY / Y) / (1 / s)).
+/// # Параметры
+/// * `system` - Модель СМО.
+/// # Возвращаемое значение
+/// Чувствительности в порядке строк отчёта, тип: `Result<Vec<CharacteristicSensitivity>, Box<dyn Error>>`.
+pub fn characteristics_sensitivity(system: &QueuingSystem) -> Result<Vec<CharacteristicSensitivity>, Box<dyn Error>> {
+    let report_for = |num_channels: i32, queue_size: i32| -> Result<Vec<(&'static str, f64)>, Box<dyn Error>> {
+        Ok(characteristics_report(&QueuingSystem {
+            num_channels,
+            queue_size,
+            initial_state: Config::empty_initial_state(num_channels, queue_size),
+            ..system.clone()
+        }.stationary_characteristics()?))
+    };
+    let (lambda_rate, mu_rate) = (system.lambda_rate, system.mu_rate);
+    let (num_channels, queue_size) = (system.num_channels, system.queue_size);
+    let more_channels = report_for(num_channels + 1, queue_size)?;
+    let longer_queue = report_for(num_channels, queue_size + 1)?;
+
+    let characteristics = system.stationary_characteristics()?;
+    let d_lambdas = characteristics_derivative(system, &characteristics, SensitivityParameter::Lambda)?;
+    let d_mus = characteristics_derivative(system, &characteristics, SensitivityParameter::Mu)?;
+
+    Ok(characteristics_report(&characteristics)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (name, value))| {
+            let (d_lambda, d_mu) = (d_lambdas[index], d_mus[index]);
+            let delta_channels = more_channels[index].1 - value;
+            let delta_queue = longer_queue[index].1 - value;
+            CharacteristicSensitivity {
+                name,
+                value,
+                d_lambda,
+                d_mu,
+                delta_channels,
+                delta_queue,
+                elasticity_lambda: elasticity(d_lambda, lambda_rate, value),
+                elasticity_mu: elasticity(d_mu, mu_rate, value),
+                elasticity_channels: elasticity(delta_channels, num_channels as f64, value),
+                elasticity_queue: elasticity(delta_queue, queue_size as f64, value),
+            }
+        })
+        .collect())
+}
+
+/// Форматирует чувствительности в таблицу-«торнадо»: строки упорядочены по убыванию
+/// наибольшей по модулю эластичности, чтобы самые чувствительные характеристики были сверху.
+/// # Параметры
+/// * `sensitivities` - Чувствительности характеристик.
+/// # Возвращаемое значение
+/// Текстовая таблица, тип: `String`.
+pub fn format_tornado_table(sensitivities: &[CharacteristicSensitivity]) -> String {
+    let max_elasticity = |s: &CharacteristicSensitivity| [s.elasticity_lambda, s.elasticity_mu, s.elasticity_channels, s.elasticity_queue]
+        .iter()
+        .fold(0.0_f64, |acc, e| acc.max(e.abs()));
+    let mut sorted: Vec<&CharacteristicSensitivity> = sensitivities.iter().collect();
+    sorted.sort_by(|a, b| max_elasticity(b).total_cmp(&max_elasticity(a)));
+
+    let name_width = sensitivities.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+    let header = format!(
+        "{:name_width$} | {:>12} | {:>12} | {:>12} | {:>12} | {:>12} | {:>8} | {:>8} | {:>8} | {:>8}",
+        "", "значение", "∂/∂λ", "∂/∂μ", "Δ(s+1)", "Δ(n+1)", "E_λ", "E_μ", "E_s", "E_n"
+    );
+    let rows = sorted.iter().map(|s| format!(
+        "{:name_width$} | {:>12.6} | {:>12.6} | {:>12.6} | {:>12.6} | {:>12.6} | {:>8.3} | {:>8.3} | {:>8.3} | {:>8.3}",
+        s.name, s.value, s.d_lambda, s.d_mu, s.delta_channels, s.delta_queue,
+        s.elasticity_lambda, s.elasticity_mu, s.elasticity_channels, s.elasticity_queue
+    ));
+
+    std::iter::once(header).chain(rows).collect::<Vec<String>>().join("\n")
+}
+
+/// Строит диаграмму-«торнадо» эластичностей одной характеристики по λ, μ, s и n:
+/// изменение характеристики в процентах при росте каждого параметра на 1%.
+/// # Параметры
+/// * `sensitivity` - Чувствительность характеристики.
+/// * `options` - Настройки построения графика.
+/// # Возвращаемое значение
+/// Результат построения графика, тип: `Result<(), Box<dyn Error>>`.
+pub fn plot_tornado(sensitivity: &CharacteristicSensitivity, options: &PlotOptions) -> Result<(), Box<dyn Error>> {
+    options.render(&TornadoFigure { sensitivity })
+}
+
+/// Диаграмма-«торнадо» для `plot_tornado`.
+struct TornadoFigure<'a> {
+    sensitivity: &'a CharacteristicSensitivity,
+}
+
+impl Figure for TornadoFigure<'_> {
+    fn default_name(&self) -> &str {
+        "elasticity_tornado"
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let sensitivity = self.sensitivity;
+
+        let mut bars = [
+            (options.text("λ (поток)", "λ (arrivals)"), SensitivityParameter::Lambda),
+            (options.text("μ (обслуживание)", "μ (service)"), SensitivityParameter::Mu),
+            (options.text("s (каналы)", "s (channels)"), SensitivityParameter::Channels),
+            (options.text("n (очередь)", "n (queue)"), SensitivityParameter::Queue),
+        ].map(|(label, parameter)| (label, sensitivity.elasticity(parameter)));
+        bars.sort_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+        let max_x = bars.iter().fold(1e-9_f64, |acc, (_, e)| acc.max(e.abs())) * 1.1;
+
+        let mut chart = ChartBuilder::on(root)
+            .caption(options.title_or(self.default_name(), sensitivity.name, english_characteristic_name(sensitivity.name)), ("sans-serif", 30).into_font())
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(140)
+            .build_cartesian_2d(-max_x..max_x, (0..bars.len()).into_segmented())?;
+
+        chart.configure_mesh()
+            .disable_y_mesh()
+            .x_desc(options.x_label_or(self.default_name(), "Эластичность", "Elasticity"))
+            .y_desc(options.y_label_or(self.default_name(), "Параметр", "Parameter"))
+            .y_label_formatter(&|segment| match segment {
+                SegmentValue::CenterOf(i) => bars.get(*i).map_or(String::new(), |(label, _)| label.to_string()),
+                _ => String::new(),
+            })
+            .draw()?;
+
+        chart.draw_series(bars.iter().enumerate().map(|(i, &(_, e))| {
+            let color = if e >= 0.0 { RED } else { BLUE };
+            Rectangle::new(
+                [(0.0, SegmentValue::Exact(i)), (e, SegmentValue::Exact(i + 1))],
+                color.mix(0.7).filled(),
+            )
+        }))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch_arrivals::BatchRejectionPolicy;
+
+    /// Сравнивает точные производные с центральными разностями по λ и μ.
+    fn assert_matches_finite_differences(system: &QueuingSystem) {
+        let h = 1e-5;
+        let report_at = |lambda_rate: f64, mu_rate: f64| {
+            characteristics_report(&QueuingSystem { lambda_rate, mu_rate, ..system.clone() }.stationary_characteristics().unwrap())
+        };
+        let (lambda, mu) = (system.lambda_rate, system.mu_rate);
+        let difference = |plus: Vec<(&'static str, f64)>, minus: Vec<(&'static str, f64)>| -> Vec<f64> {
+            plus.iter().zip(minus.iter()).map(|((_, plus), (_, minus))| (plus - minus) / (2.0 * h)).collect()
+        };
+        let d_lambdas = difference(report_at(lambda + h, mu), report_at(lambda - h, mu));
+        let d_mus = difference(report_at(lambda, mu + h), report_at(lambda, mu - h));
+
+        for ((sensitivity, d_lambda), d_mu) in characteristics_sensitivity(system).unwrap().iter().zip(d_lambdas).zip(d_mus) {
+            assert!((sensitivity.d_lambda - d_lambda).abs() < 1e-6 * (1.0 + d_lambda.abs()), "∂/∂λ {}: {} ≠ {}", sensitivity.name, sensitivity.d_lambda, d_lambda);
+            assert!((sensitivity.d_mu - d_mu).abs() < 1e-6 * (1.0 + d_mu.abs()), "∂/∂μ {}: {} ≠ {}", sensitivity.name, sensitivity.d_mu, d_mu);
+        }
+    }
+
+    fn system(abandonment_rate: f64, batch_arrivals: Option<BatchArrivals>) -> QueuingSystem {
+        QueuingSystem::new(3.0, 1.2, abandonment_rate, None, batch_arrivals, 2, 4, Config::empty_initial_state(2, 4), 5, 100, 0.01)
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        assert_matches_finite_differences(&system(0.0, None));
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences_with_abandonment() {
+        assert_matches_finite_differences(&system(0.4, None));
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences_with_batches() {
+        let batch_arrivals = BatchArrivals::new(vec![0.5, 0.3, 0.2], BatchRejectionPolicy::Partial).unwrap();
+        assert_matches_finite_differences(&system(0.0, Some(batch_arrivals)));
+    }
+}
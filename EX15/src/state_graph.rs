@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fs;
+use std::path::Path;
+
+use nalgebra::DMatrix;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::plot_options::{Figure, PlotOptions};
+
+/// Переход графа состояний с интенсивностью `rate`.
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: usize,
+    pub to: usize,
+    pub rate: f64,
+    pub symbol: Option<String>, // Символьная запись интенсивности, например «3μ»
+}
+
+impl Transition {
+    /// Подпись дуги: «3μ = 15», если известна символьная запись, иначе числовое значение.
+    pub fn label(&self) -> String {
+        match &self.symbol {
+            Some(symbol) => format!("{} = {}", symbol, format_rate(self.rate)),
+            None => format_rate(self.rate),
+        }
+    }
+}
+
+/// Размеченный граф состояний цепи Маркова: вершины — состояния, дуги — интенсивности переходов.
+#[derive(Debug, Clone)]
+pub struct StateGraph {
+    pub labels: Vec<String>,
+    pub transitions: Vec<Transition>,
+}
+
+impl StateGraph {
+    /// Строит граф по матрице уравнений Колмогорова: ненулевой внедиагональный
+    /// элемент A[to][from] задаёт переход из S_from в S_to.
+    /// # Параметры
+    /// * `matrix` - Матрица правых частей уравнений Колмогорова.
+    /// * `labels` - Подписи состояний в порядке строк матрицы.
+    /// # Возвращаемое значение
+    /// Граф состояний, тип: `StateGraph`.
+    pub fn from_matrix(matrix: &DMatrix<f64>, labels: Vec<String>) -> StateGraph {
+        let transitions = (0..matrix.ncols())
+            .flat_map(|from| (0..matrix.nrows()).map(move |to| (from, to)))
+            .filter(|&(from, to)| from != to && matrix[(to, from)] > 0.0)
+            .map(|(from, to)| Transition { from, to, rate: matrix[(to, from)], symbol: None })
+            .collect();
+
+        StateGraph { labels, transitions }
+    }
+
+    /// Проверяет, что переходы возможны только между соседними состояниями (процесс гибели и размножения).
+    pub fn is_birth_death(&self) -> bool {
+        self.transitions.iter().all(|t| t.from.abs_diff(t.to) == 1)
+    }
+
+    /// Формирует описание графа на языке Graphviz DOT.
+    /// # Возвращаемое значение
+    /// Текст DOT, тип: `String`.
+    pub fn to_dot(&self) -> String {
+        let rank_direction = if self.is_birth_death() { "LR" } else { "TB" };
+        let nodes = self.labels.iter()
+            .enumerate()
+            .map(|(i, label)| format!("    s{} [label=\"{}\"];", i, label.replace('"', "\\\"")));
+        let edges = self.transitions.iter()
+            .map(|t| format!("    s{} -> s{} [label=\"{}\"];", t.from, t.to, t.label()));
+
+        std::iter::once(format!("digraph states {{\n    rankdir={};\n    node [shape=box];", rank_direction))
+            .chain(nodes)
+            .chain(edges)
+            .chain(std::iter::once("}".to_string()))
+            .collect::<Vec<String>>()
+            .join("\n") + "\n"
+    }
+
+    /// Сохраняет граф в DOT-файл для отрисовки утилитой Graphviz.
+    /// # Параметры
+    /// * `path` - Путь к DOT-файлу.
+    /// # Возвращаемое значение
+    /// Результат записи файла, тип: `Result<(), Box<dyn Error>>`.
+    pub fn export_dot<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_dot())?;
+        Ok(())
+    }
+
+    /// Координаты вершин: процесс гибели и размножения располагается в линию,
+    /// произвольный граф — по окружности. Размер холста растёт с числом состояний.
+    fn layout(&self) -> ((u32, u32), Vec<(f64, f64)>) {
+        let n = self.labels.len().max(1);
+        if self.is_birth_death() {
+            let spacing = 160.0;
+            let width = (n as f64 * spacing + 80.0) as u32;
+            let positions = (0..n).map(|i| (80.0 + spacing * i as f64, 200.0)).collect();
+            ((width, 400), positions)
+        } else {
+            let radius = (n as f64 * 140.0 / (2.0 * PI)).max(200.0);
+            let size = (2.0 * radius + 240.0) as u32;
+            let center = size as f64 / 2.0;
+            let positions = (0..n)
+                .map(|i| {
+                    let angle = 2.0 * PI * i as f64 / n as f64 - PI / 2.0;
+                    (center + radius * angle.cos(), center + radius * angle.sin())
+                })
+                .collect();
+            ((size, size), positions)
+        }
+    }
+
+    /// Рисует граф состояний в файл.
+    /// Встречные дуги смещаются в разные стороны, подпись интенсивности ставится
+    /// по центру дуги со стороны смещения, чтобы подписи встречных переходов не накладывались.
+    /// # Параметры
+    /// * `name` - Имя файла графика без расширения.
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графа, тип: `Result<(), Box<dyn Error>>`.
+    pub fn plot(&self, name: &str, options: &PlotOptions) -> Result<(), Box<dyn Error>> {
+        options.render(&StateGraphFigure { graph: self, name })
+    }
+
+    /// Рисует граф состояний в строку SVG.
+    /// # Параметры
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Текст SVG, тип: `Result<String, Box<dyn Error>>`.
+    pub fn to_svg(&self, options: &PlotOptions) -> Result<String, Box<dyn Error>> {
+        options.render_svg(&StateGraphFigure { graph: self, name: "state_graph" })
+    }
+}
+
+/// Граф состояний для `StateGraph::plot`.
+struct StateGraphFigure<'a> {
+    graph: &'a StateGraph,
+    name: &'a str,
+}
+
+impl Figure for StateGraphFigure<'_> {
+    fn default_name(&self) -> &str {
+        self.name
+    }
+
+    fn default_size(&self) -> (u32, u32) {
+        // Дополнительная полоса сверху отводится под заголовок
+        let (width, height) = self.graph.layout().0;
+        (width, height + 50)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let (natural_size, positions) = self.graph.layout();
+        let root_area = root.titled(&options.title_or(self.default_name(), "Граф состояний", "State Graph"), ("sans-serif", 30).into_font())?;
+        // Раскладка масштабируется под размер изображения, если он задан в настройках
+        let (width, height) = root_area.dim_in_pixel();
+        let scale = (width as f64 / natural_size.0 as f64).min(height as f64 / natural_size.1 as f64);
+        let positions: Vec<(f64, f64)> = positions.iter().map(|&(x, y)| (x * scale, y * scale)).collect();
+
+        let node_radius = 28.0;
+        let arrow_size = 8.0;
+        let text_style = TextStyle::from(("sans-serif", 14).into_font()).color(&BLACK);
+
+        for t in &self.graph.transitions {
+            let (x1, y1) = positions[t.from];
+            let (x2, y2) = positions[t.to];
+            let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt();
+            if length == 0.0 {
+                continue;
+            }
+            let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+            // Смещение вправо по направлению движения разводит встречные дуги
+            let (nx, ny) = (-uy * 6.0, ux * 6.0);
+            let start = (x1 + ux * node_radius + nx, y1 + uy * node_radius + ny);
+            let end = (x2 - ux * node_radius + nx, y2 - uy * node_radius + ny);
+            let color = if t.to > t.from { BLUE } else { RED };
+
+            root_area.draw(&PathElement::new(
+                vec![(start.0 as i32, start.1 as i32), (end.0 as i32, end.1 as i32)],
+                color.stroke_width(2),
+            ))?;
+            root_area.draw(&Polygon::new(
+                vec![
+                    (end.0 as i32, end.1 as i32),
+                    ((end.0 - ux * arrow_size * 1.5 + uy * arrow_size / 2.0) as i32, (end.1 - uy * arrow_size * 1.5 - ux * arrow_size / 2.0) as i32),
+                    ((end.0 - ux * arrow_size * 1.5 - uy * arrow_size / 2.0) as i32, (end.1 - uy * arrow_size * 1.5 + ux * arrow_size / 2.0) as i32),
+                ],
+                color.filled(),
+            ))?;
+
+            let label = t.label();
+            let (label_width, label_height) = root_area.estimate_text_size(&label, &text_style)?;
+            let label_x = start.0 + (end.0 - start.0) * 0.5 + nx * 2.5;
+            let label_y = start.1 + (end.1 - start.1) * 0.5 + ny * 2.5;
+            root_area.draw_text(
+                &label,
+                &text_style.color(&color),
+                (label_x as i32 - label_width as i32 / 2, label_y as i32 - label_height as i32 / 2),
+            )?;
+        }
+
+        for (label, &(x, y)) in self.graph.labels.iter().zip(positions.iter()) {
+            root_area.draw(&Circle::new((x as i32, y as i32), node_radius as i32, WHITE.filled()))?;
+            root_area.draw(&Circle::new((x as i32, y as i32), node_radius as i32, BLACK.stroke_width(2)))?;
+            let (text_width, text_height) = root_area.estimate_text_size(label, &text_style)?;
+            root_area.draw_text(label, &text_style, (x as i32 - text_width as i32 / 2, y as i32 - text_height as i32 / 2))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Форматирует интенсивность без лишних нулей: 30, 2.5, 0.125.
+pub fn format_rate(rate: f64) -> String {
+    let formatted = format!("{:.3}", rate);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
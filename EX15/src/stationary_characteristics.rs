@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+
+use nalgebra::DVector;
+
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+
+/// Характеристики СМО, вычисленные по численному стационарному распределению
+/// числа заявок в системе p_0..p_{s+n}. Используется моделями, для которых
+/// нет формул в замкнутом виде, но состояния можно свести к числу заявок.
+pub struct StationaryCharacteristics {
+    pub lambda_rate: f64,          // Интенсивность потока заявок
+    pub mu_rate: f64,              // Средняя интенсивность обслуживания одним каналом
+    pub num_channels: i32,         // Количество каналов
+    pub queue_size: i32,           // Ограничение на размер очереди
+    pub time: i32,                 // Время
+    pub distribution: DVector<f64>, // Вероятности числа заявок в системе
+    pub throughput: f64,           // Интенсивность потока обслуженных заявок
+    pub admitted_rate: f64,        // Интенсивность потока принятых заявок: обслуженные и ушедшие из очереди
+    pub busy_channels: Option<f64>, // Среднее число занятых каналов, если не сводится к min(i, s)
+    pub queue_length: Option<f64>,  // Средняя длина очереди, если не сводится к (i - s)⁺
+}
+
+impl StationaryCharacteristics {
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               num_channels: i32,
+               queue_size: i32,
+               time: i32,
+               distribution: DVector<f64>,
+               throughput: f64
+    ) -> StationaryCharacteristics {
+
+        StationaryCharacteristics {
+            lambda_rate,
+            mu_rate,
+            num_channels,
+            queue_size,
+            time,
+            distribution,
+            throughput,
+            admitted_rate: throughput,
+            busy_channels: None,
+            queue_length: None
+        }
+    }
+
+    /// Задаёт среднее число занятых каналов и среднюю длину очереди, вычисленные по полному
+    /// пространству состояний. Нужно моделям, в которых число занятых каналов не определяется
+    /// числом заявок, например при неисправных каналах.
+    /// # Параметры
+    /// * `busy_channels` - Среднее число занятых каналов.
+    /// * `queue_length` - Среднее число заявок в очереди.
+    /// # Возвращаемое значение
+    /// Характеристики с заданными средними, тип: `StationaryCharacteristics`.
+    pub fn with_occupancy(self, busy_channels: f64, queue_length: f64) -> StationaryCharacteristics {
+        StationaryCharacteristics {
+            busy_channels: Some(busy_channels),
+            queue_length: Some(queue_length),
+            ..self
+        }
+    }
+
+    /// Задаёт интенсивность потока принятых заявок, если часть из них покидает систему
+    /// без обслуживания, например уходит из очереди с интенсивностью θ: X + θ·Lq.
+    /// По умолчанию она равна пропускной способности.
+    /// # Параметры
+    /// * `admitted_rate` - Интенсивность потока принятых заявок.
+    /// # Возвращаемое значение
+    /// Характеристики с заданной интенсивностью, тип: `StationaryCharacteristics`.
+    pub fn with_admitted_rate(self, admitted_rate: f64) -> StationaryCharacteristics {
+        StationaryCharacteristics {
+            admitted_rate,
+            ..self
+        }
+    }
+}
+
+impl QueuingSystemCharacteristics for StationaryCharacteristics {
+    /// 1
+    /// Вычисляет коэффициент загрузки СМО.
+    /// # Возвращаемое значение
+    /// Коэффициент загрузки системы СМО, тип: `f64`.
+    fn calculate_load_factor(&self) -> f64 {
+        self.lambda_rate / self.mu_rate
+    }
+
+    /// 2
+    /// Вычисляет вероятность простоя системы (P0).
+    /// # Возвращаемое значение
+    /// Вероятность простоя системы, тип: `f64`.
+    fn calculate_probability_of_downtime(&self) -> f64 {
+        self.distribution[0]
+    }
+
+    /// Вычисляет факториал числа.
+    /// # Параметры
+    /// * `n` - Число, для которого вычисляется факториал.
+    /// # Возвращаемое значение
+    /// Факториал заданного числа, тип: `u64`.
+    fn factorial(n: u64) -> u64 {
+        (1..=n).product()
+    }
+
+    /// 3
+    /// Вероятность того, что i каналов заняты и нет очереди.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_probabilities(&self) -> BTreeMap<String, f64> {
+        (0..=self.num_channels as usize)
+            .map(|i| (format!("P_{}", i), self.distribution[i]))
+            .collect()
+    }
+
+    /// 4
+    /// Вероятность того, что все s каналов заняты и очередь длины i.
+    /// # Возвращаемое значение
+    /// Ключ(состояние системы с очередью) и значение(вероятность этого состояния), тип: `BTreeMap<String, f64>`.
+    fn calculate_queue_probabilities(&self) -> BTreeMap<String, f64> {
+        let s = self.num_channels as usize;
+        (1..=self.queue_size as usize)
+            .map(|i| (format!("P{}", i), self.distribution[s + i]))
+            .collect()
+    }
+
+    /// 5
+    /// Вероятность того, что поступившая заявка не будет обслужена.
+    /// Для одиночных поступлений без уходов из очереди совпадает с вероятностью последнего состояния.
+    /// # Возвращаемое значение
+    /// 1 - λ_эфф / λ, тип: `f64`.
+    fn calculate_rejection_probability(&self) -> f64 {
+        1.0 - self.throughput / self.lambda_rate
+    }
+
+    /// 6
+    /// Вычисляет среднее количество заявок, поступающих в систему за время T.
+    /// # Возвращаемое значение
+    /// Среднее количество заявок за указанный период времени, тип: `f64`.
+    fn calculate_average_incoming_requests_during_t(&self) -> f64 {
+        self.lambda_rate * self.time as f64
+    }
+
+    /// 7
+    /// Вычисляет среднее время обслуживания одной заявки.
+    /// # Возвращаемое значение
+    /// Среднее время, необходимое для обслуживания одной заявки, тип: `f64`.
+    fn calculate_average_service_time_per_request(&self) -> f64 {
+        1.0 / self.mu_rate
+    }
+
+    /// 8
+    /// Вычисляет среднее время обслуживания одним каналом заявок, поступивших за время T.
+    /// # Возвращаемое значение
+    /// Среднее время обслуживания заявок одним каналом за время T, тип: `f64`.
+    fn average_service_time_per_channel_for_t(&self) -> f64 {
+        self.calculate_load_factor() * self.time as f64
+    }
+
+    /// 9
+    /// Вычисляет среднее число занятых каналов в системе.
+    /// # Возвращаемое значение
+    /// Σ min(i, s)·p_i, если не задано `with_occupancy`, тип: `f64`.
+    fn calculate_average_busy_channels(&self) -> f64 {
+        if let Some(busy_channels) = self.busy_channels {
+            return busy_channels;
+        }
+        let s = self.num_channels as usize;
+        self.distribution.iter()
+            .enumerate()
+            .map(|(i, p)| i.min(s) as f64 * p)
+            .sum()
+    }
+
+    /// 10
+    /// Вычисляет среднее количество заявок в очереди.
+    /// # Возвращаемое значение
+    /// Σ (i - s)·p_i по i > s, если не задано `with_occupancy`, тип: `f64`.
+    fn calculate_average_number_of_requests_in_queue(&self) -> f64 {
+        if let Some(queue_length) = self.queue_length {
+            return queue_length;
+        }
+        let s = self.num_channels as usize;
+        self.distribution.iter()
+            .enumerate()
+            .map(|(i, p)| i.saturating_sub(s) as f64 * p)
+            .sum()
+    }
+
+    /// 12
+    /// Вычисляет среднее время пребывания заявки в очереди.
+    /// # Возвращаемое значение
+    /// Lq / λ_пр по формуле Литтла для потока принятых заявок, тип: `f64`.
+    fn calculate_average_waiting_time_in_queue(&self) -> f64 {
+        self.calculate_average_number_of_requests_in_queue() / self.admitted_rate
+    }
+
+    /// 13
+    /// Вычисляет общее количество заявок в системе.
+    /// # Возвращаемое значение
+    /// Σ i·p_i, тип: `f64`.
+    fn calculate_total_number_of_requests(&self) -> f64 {
+        self.distribution.iter()
+            .enumerate()
+            .map(|(i, p)| i as f64 * p)
+            .sum()
+    }
+
+    /// 14
+    /// Вычисляет среднее время ожидания заявки в системе.
+    /// # Возвращаемое значение
+    /// Среднее время ожидания заявки в системе, тип: `f64`.
+    fn calculate_average_waiting_time(&self) -> f64 {
+        self.calculate_average_waiting_time_in_queue()
+    }
+
+    /// 15
+    /// Вычисляет среднее время пребывания заявки в системе.
+    /// # Возвращаемое значение
+    /// L / λ_пр по формуле Литтла для потока принятых заявок, тип: `f64`.
+    fn calculate_average_time_in_system(&self) -> f64 {
+        self.calculate_total_number_of_requests() / self.admitted_rate
+    }
+}
@@ -0,0 +1,118 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::plot_options::{palette, Figure, PlotOptions};
+
+/// Характеристики СМО во времени, полученные из траектории уравнений Колмогорова.
+#[derive(Debug, Clone)]
+pub struct TransientMetrics {
+    pub times: Vec<f64>,                 // Моменты времени
+    pub number_in_system: Vec<f64>,      // Среднее число заявок в системе L(t)
+    pub queue_length: Vec<f64>,          // Средняя длина очереди Lq(t)
+    pub busy_channels: Vec<f64>,         // Среднее число занятых каналов
+    pub rejection_probability: Vec<f64>, // Вероятность потери поступающей заявки
+    pub throughput: Vec<f64>,            // Интенсивность потока обслуженных заявок
+}
+
+impl TransientMetrics {
+    /// Сохраняет характеристики в CSV-файл: строка на момент времени, столбец на характеристику.
+    /// # Параметры
+    /// * `path` - Путь к CSV-файлу.
+    /// # Возвращаемое значение
+    /// Результат записи файла, тип: `Result<(), Box<dyn Error>>`.
+    pub fn export_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn Error>> {
+        let header = "time,number_in_system,queue_length,busy_channels,rejection_probability,throughput".to_string();
+        let rows = (0..self.times.len()).map(|k| format!(
+            "{},{},{},{},{},{}",
+            self.times[k], self.number_in_system[k], self.queue_length[k],
+            self.busy_channels[k], self.rejection_probability[k], self.throughput[k]
+        ));
+
+        fs::write(path, std::iter::once(header).chain(rows).collect::<Vec<String>>().join("\n") + "\n")?;
+        Ok(())
+    }
+
+    /// Строит графики характеристик во времени: средние числа заявок и занятых каналов,
+    /// вероятность потери и пропускную способность на отдельных панелях.
+    /// # Параметры
+    /// * `options` - Настройки построения графика.
+    /// # Возвращаемое значение
+    /// Результат построения графика, тип: `Result<(), Box<dyn Error>>`.
+    pub fn plot(&self, options: &PlotOptions) -> Result<(), Box<dyn Error>> {
+        options.render(self)
+    }
+}
+
+impl Figure for TransientMetrics {
+    fn default_name(&self) -> &str {
+        "transient_metrics"
+    }
+
+    fn default_size(&self) -> (u32, u32) {
+        (1024, 1152)
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: &DrawingArea<DB, Shift>, options: &PlotOptions) -> Result<(), Box<dyn Error>>
+    where
+        DB::ErrorType: 'static,
+    {
+        root.fill(&WHITE)?;
+        let root = root.titled(&options.title_or(self.default_name(), "Характеристики СМО во времени", "Transient Metrics"), ("sans-serif", 40).into_font())?;
+
+        let panels = [
+            (options.text("Среднее число", "Expected count"), vec![
+                (options.text("Заявок в системе", "In system"), &self.number_in_system),
+                (options.text("Заявок в очереди", "In queue"), &self.queue_length),
+                (options.text("Занятых каналов", "Busy channels"), &self.busy_channels),
+            ]),
+            (options.text("Вероятность", "Probability"), vec![
+                (options.text("Вероятность потери", "Rejection probability"), &self.rejection_probability),
+            ]),
+            (options.text("Интенсивность", "Rate"), vec![
+                (options.text("Пропускная способность", "Throughput"), &self.throughput),
+            ]),
+        ];
+        let (t_start, t_end) = (self.times.first().copied().unwrap_or(0.0), self.times.last().copied().unwrap_or(1.0));
+
+        for (area, (y_label, series)) in root.split_evenly((panels.len(), 1)).iter().zip(panels.iter()) {
+            let max_y = series.iter()
+                .flat_map(|(_, values)| values.iter().cloned())
+                .fold(0.0_f64, f64::max)
+                .max(1e-9) * 1.1;
+
+            let mut chart = ChartBuilder::on(area)
+                .margin(10)
+                .x_label_area_size(40)
+                .y_label_area_size(60)
+                .build_cartesian_2d(t_start..t_end, 0.0..max_y)?;
+
+            chart.configure_mesh()
+                .x_desc(options.x_label_or(self.default_name(), "Время", "Time"))
+                .y_desc(*y_label)
+                .draw()?;
+
+            for (&(label, values), color) in series.iter().zip(palette(series.len())) {
+                chart
+                    .draw_series(LineSeries::new(
+                        self.times.iter().cloned().zip(values.iter().cloned()),
+                        color.stroke_width(2),
+                    ))?
+                    .label(label)
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+            }
+
+            chart
+                .configure_series_labels()
+                .position(options.legend_position.to_series_label_position())
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()?;
+        }
+
+        Ok(())
+    }
+}
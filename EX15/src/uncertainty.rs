@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::thread;
+
+use nalgebra::DVector;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::queuing_system::QueuingSystem;
+use crate::queuing_system_characteristics::QueuingSystemCharacteristics;
+use crate::rate_distribution::RateDistribution;
+use crate::statistics::percentile;
+
+/// Полоса квантилей характеристики: нижний квантиль, медиана и верхний квантиль.
+#[derive(Debug, Clone, Copy)]
+pub struct PercentileBand {
+    pub lower: f64,
+    pub median: f64,
+    pub upper: f64,
+}
+
+impl PercentileBand {
+    /// Строит полосу квантилей по выборке значений.
+    /// # Параметры
+    /// * `values` - Значения характеристики по всем выборкам параметров.
+    /// * `lower_level` - Уровень нижнего квантиля, например 0.05.
+    /// * `upper_level` - Уровень верхнего квантиля, например 0.95.
+    /// # Возвращаемое значение
+    /// Полоса квантилей, тип: `PercentileBand`.
+    pub fn from_values(values: &[f64], lower_level: f64, upper_level: f64) -> PercentileBand {
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        PercentileBand {
+            lower: percentile(&sorted, lower_level),
+            median: percentile(&sorted, 0.5),
+            upper: percentile(&sorted, upper_level),
+        }
+    }
+}
+
+/// Результат распространения неопределённости λ и μ на характеристики СМО.
+#[derive(Debug, Clone)]
+pub struct UncertaintyAnalysis {
+    pub num_samples: usize,
+    pub rejection_probability: PercentileBand,
+    pub average_queue_length: PercentileBand,
+    pub average_waiting_time: PercentileBand,
+    pub transient_lower: Vec<(f64, DVector<f64>)>,  // Пары (t, нижний квантиль вероятностей состояний) в моменты 0, h, 2h, ...
+    pub transient_median: Vec<(f64, DVector<f64>)>, // Медиана вероятностей состояний
+    pub transient_upper: Vec<(f64, DVector<f64>)>,  // Верхний квантиль вероятностей состояний
+}
+
+/// Значения характеристик и траектория для одной выборки параметров.
+struct SampleResult {
+    rejection_probability: f64,
+    average_queue_length: f64,
+    average_waiting_time: f64,
+    states: Vec<(f64, DVector<f64>)>,
+}
+
+/// Вычисляет характеристики по стационарному распределению и траекторию для одной выборки λ и μ.
+fn evaluate(system: &QueuingSystem, lambda_rate: f64, mu_rate: f64) -> Result<SampleResult, Box<dyn Error>> {
+    let sample_system = QueuingSystem { lambda_rate, mu_rate, ..system.clone() };
+    let characteristics = sample_system.stationary_characteristics()?;
+    Ok(SampleResult {
+        rejection_probability: characteristics.calculate_rejection_probability(),
+        average_queue_length: characteristics.calculate_average_number_of_requests_in_queue(),
+        average_waiting_time: characteristics.calculate_average_waiting_time_in_queue(),
+        states: sample_system.integrate_system(),
+    })
+}
+
+/// Распространяет неопределённость λ и μ методом Монте-Карло.
+/// Все выборки параметров генерируются заранее одним генератором, поэтому результат
+/// не зависит от числа потоков; модель для выборок вычисляется параллельно.
+/// # Параметры
+/// * `system` - Модель СМО, задающая структуру системы и параметры интегрирования.
+/// * `lambda` - Распределение интенсивности потока заявок.
+/// * `mu` - Распределение интенсивности обслуживания.
+/// * `num_samples` - Количество выборок.
+/// * `band` - Уровни нижнего и верхнего квантилей, например (0.05, 0.95).
+/// * `seed` - Начальное значение генератора случайных чисел.
+/// # Возвращаемое значение
+/// Полосы квантилей характеристик и траектории либо ошибка, если распределение λ или μ
+/// задано некорректно или модель не решается для какой-либо выборки, тип: `Result<UncertaintyAnalysis, Box<dyn Error>>`.
+pub fn propagate_uncertainty(
+    system: &QueuingSystem,
+    lambda: &RateDistribution,
+    mu: &RateDistribution,
+    num_samples: usize,
+    band: (f64, f64),
+    seed: u64
+) -> Result<UncertaintyAnalysis, Box<dyn Error>> {
+    lambda.validate()?;
+    mu.validate()?;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let parameters: Vec<(f64, f64)> = (0..num_samples)
+        .map(|_| (lambda.sample(&mut rng), mu.sample(&mut rng)))
+        .collect();
+
+    let num_threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = parameters.len().div_ceil(num_threads).max(1);
+    // `Box<dyn Error>` нельзя передать между потоками, поэтому ошибки выборок возвращаются текстом
+    let results: Vec<SampleResult> = thread::scope(|scope| {
+        let handles: Vec<_> = parameters.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter()
+                    .map(|&(lambda_rate, mu_rate)| evaluate(system, lambda_rate, mu_rate).map_err(|error| error.to_string()))
+                    .collect::<Result<Vec<_>, String>>()
+            }))
+            .collect();
+        handles.into_iter()
+            .map(|handle| handle.join().expect("Monte Carlo worker panicked"))
+            .collect::<Result<Vec<_>, String>>()
+    })?.into_iter().flatten().collect();
+
+    let band_of = |value: fn(&SampleResult) -> f64| {
+        let values: Vec<f64> = results.iter().map(value).collect();
+        PercentileBand::from_values(&values, band.0, band.1)
+    };
+
+    // Все выборки интегрируются на одной сетке времени, поэтому моменты берутся из первой траектории
+    let times: Vec<f64> = results.first().map_or_else(Vec::new, |result| result.states.iter().map(|(t, _)| *t).collect());
+    let num_states = results.first().and_then(|result| result.states.first()).map_or(0, |(_, state)| state.len());
+    let transient: Vec<Vec<PercentileBand>> = (0..times.len())
+        .map(|step| (0..num_states).map(|i| {
+            let values: Vec<f64> = results.iter().map(|result| result.states[step].1[i]).collect();
+            PercentileBand::from_values(&values, band.0, band.1)
+        }).collect())
+        .collect();
+    let transient_of = |value: fn(&PercentileBand) -> f64| times.iter().zip(transient.iter())
+        .map(|(&t, bands)| (t, DVector::from_iterator(num_states, bands.iter().map(value))))
+        .collect();
+
+    Ok(UncertaintyAnalysis {
+        num_samples,
+        rejection_probability: band_of(|result| result.rejection_probability),
+        average_queue_length: band_of(|result| result.average_queue_length),
+        average_waiting_time: band_of(|result| result.average_waiting_time),
+        transient_lower: transient_of(|band| band.lower),
+        transient_median: transient_of(|band| band.median),
+        transient_upper: transient_of(|band| band.upper),
+    })
+}
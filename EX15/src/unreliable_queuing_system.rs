@@ -0,0 +1,203 @@
+use std::error::Error;
+use nalgebra::{DMatrix, DVector};
+
+use crate::markov_chain;
+use crate::state_graph::StateGraph;
+use crate::stationary_characteristics::StationaryCharacteristics;
+
+/// Состояние СМО с отказами каналов: число заявок в системе и число исправных каналов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnreliableState {
+    pub customers: i32, // Число заявок в системе
+    pub working: i32,   // Число исправных каналов
+}
+
+/// СМО M/M/s/n, каналы которой отказывают и восстанавливаются.
+/// Заявка, обслуживание которой прервано отказом канала, возвращается в систему
+/// и дообслуживается свободным исправным каналом, поэтому скорость обслуживания
+/// в состоянии (k, w) равна min(k, w)·μ.
+pub struct UnreliableQueuingSystem {
+    pub lambda_rate: f64,  // Интенсивность потока заявок
+    pub mu_rate: f64,      // Интенсивность обработки одним каналом
+    pub failure_rate: f64, // Интенсивность отказов одного исправного канала
+    pub repair_rate: f64,  // Интенсивность восстановления одного неисправного канала
+    pub num_channels: i32, // Количество каналов
+    pub queue_size: i32,   // Ограничение на размер очереди
+    pub time: i32,         // Время
+    pub num_iterations: i32, // Количество итерации
+    pub step_size: f64     // Шаг
+}
+
+impl UnreliableQueuingSystem {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(lambda_rate: f64,
+               mu_rate: f64,
+               failure_rate: f64,
+               repair_rate: f64,
+               num_channels: i32,
+               queue_size: i32,
+               time: i32,
+               num_iterations: i32,
+               step_size: f64
+    ) -> UnreliableQueuingSystem {
+
+        UnreliableQueuingSystem {
+            lambda_rate,
+            mu_rate,
+            failure_rate,
+            repair_rate,
+            num_channels,
+            queue_size,
+            time,
+            num_iterations,
+            step_size
+        }
+    }
+
+    fn capacity(&self) -> i32 {
+        self.num_channels + self.queue_size
+    }
+
+    /// Номер состояния в векторе вероятностей: состояния упорядочены по числу заявок,
+    /// внутри — по числу исправных каналов.
+    fn index(&self, state: UnreliableState) -> usize {
+        (state.customers * (self.num_channels + 1) + state.working) as usize
+    }
+
+    /// Перечисляет состояния (k, w), k = 0..s+n, w = 0..s.
+    /// # Возвращаемое значение
+    /// Состояния в порядке нумерации строк матрицы Колмогорова, тип: `Vec<UnreliableState>`.
+    pub fn states(&self) -> Vec<UnreliableState> {
+        (0..=self.capacity())
+            .flat_map(|customers| (0..=self.num_channels).map(move |working| UnreliableState { customers, working }))
+            .collect()
+    }
+
+    /// Перечисляет переходы из состояния.
+    /// # Возвращаемое значение
+    /// Пары (новое состояние, интенсивность перехода), тип: `Vec<(UnreliableState, f64)>`.
+    fn transitions(&self, state: UnreliableState) -> Vec<(UnreliableState, f64)> {
+        let UnreliableState { customers, working } = state;
+        let mut transitions = Vec::new();
+
+        if customers < self.capacity() {
+            transitions.push((UnreliableState { customers: customers + 1, working }, self.lambda_rate));
+        }
+        if customers > 0 && working > 0 {
+            transitions.push((UnreliableState { customers: customers - 1, working }, customers.min(working) as f64 * self.mu_rate));
+        }
+        if working > 0 {
+            transitions.push((UnreliableState { customers, working: working - 1 }, working as f64 * self.failure_rate));
+        }
+        if working < self.num_channels {
+            transitions.push((UnreliableState { customers, working: working + 1 }, (self.num_channels - working) as f64 * self.repair_rate));
+        }
+
+        transitions
+    }
+
+    /// Строит матрицу правых частей уравнений Колмогорова на двумерном пространстве состояний.
+    /// # Возвращаемое значение
+    /// Матрица A, для которой dp/dt = A·p, тип: `DMatrix<f64>`.
+    pub fn generate_kolmogorov_matrix(&self) -> DMatrix<f64> {
+        markov_chain::generator_matrix(&self.states(), |state| self.transitions(*state))
+    }
+
+    /// Строит граф состояний (k, w) с интенсивностями переходов.
+    /// # Возвращаемое значение
+    /// Граф состояний, тип: `StateGraph`.
+    pub fn state_graph(&self) -> StateGraph {
+        let labels = self.states().iter()
+            .map(|state| format!("({}, {})", state.customers, state.working))
+            .collect();
+        StateGraph::from_matrix(&self.generate_kolmogorov_matrix(), labels)
+    }
+
+    /// Интегрирует уравнения Колмогорова, начиная с пустой системы со всеми исправными каналами.
+    /// # Возвращаемое значение
+    /// Пары (t, p(t)) с распределениями вероятностей состояний `states()`, тип: `Vec<(f64, DVector<f64>)>`.
+    pub fn integrate_system(&self) -> Vec<(f64, DVector<f64>)> {
+        let mut initial_state = DVector::zeros(self.states().len());
+        initial_state[self.index(UnreliableState { customers: 0, working: self.num_channels })] = 1.0;
+
+        markov_chain::integrate(&self.generate_kolmogorov_matrix(), initial_state, self.step_size, self.num_iterations)
+    }
+
+    /// Вычисляет стационарное распределение по состояниям `states()`.
+    /// # Возвращаемое значение
+    /// Стационарные вероятности состояний, тип: `Result<DVector<f64>, Box<dyn Error>>`.
+    pub fn stationary_distribution(&self) -> Result<DVector<f64>, Box<dyn Error>> {
+        markov_chain::stationary_distribution(&self.generate_kolmogorov_matrix())
+    }
+
+    /// Сводит распределение по состояниям (k, w) к распределению числа заявок в системе.
+    /// # Параметры
+    /// * `distribution` - Вероятности состояний `states()`.
+    /// # Возвращаемое значение
+    /// Вероятности S_0..S_{s+n}, тип: `DVector<f64>`.
+    pub fn customer_distribution(&self, distribution: &DVector<f64>) -> DVector<f64> {
+        markov_chain::marginal_distribution(&self.states(), distribution, self.capacity() as usize + 1, |state| state.customers as usize)
+    }
+
+    /// Вычисляет коэффициент готовности каналов.
+    /// # Возвращаемое значение
+    /// Среднее число исправных каналов, делённое на s, тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_availability(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let working: f64 = self.states().iter()
+            .zip(distribution.iter())
+            .map(|(state, p)| state.working as f64 * p)
+            .sum();
+
+        Ok(working / self.num_channels as f64)
+    }
+
+    /// Вычисляет эффективную производительность системы с учётом отказов.
+    /// # Возвращаемое значение
+    /// Средняя суммарная интенсивность обслуживания исправных каналов E[w]·μ, тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_effective_capacity(&self) -> Result<f64, Box<dyn Error>> {
+        Ok(self.calculate_availability()? * self.num_channels as f64 * self.mu_rate)
+    }
+
+    /// Вычисляет вероятность отказа заявке при условии, что часть каналов неисправна.
+    /// # Возвращаемое значение
+    /// P(k = s + n | w < s), тип: `Result<f64, Box<dyn Error>>`.
+    pub fn calculate_degraded_rejection_probability(&self) -> Result<f64, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let (full, degraded) = self.states().iter()
+            .zip(distribution.iter())
+            .filter(|(state, _)| state.working < self.num_channels)
+            .fold((0.0, 0.0), |(full, degraded), (state, p)| {
+                (if state.customers == self.capacity() { full + p } else { full }, degraded + p)
+            });
+
+        Ok(if degraded > 0.0 { full / degraded } else { 0.0 })
+    }
+
+    /// Вычисляет характеристики СМО по числу заявок в системе.
+    /// Обслуживаются min(k, w) заявок, остальные (k - w)⁺ ждут, поэтому среднее число занятых
+    /// каналов и длина очереди берутся из двумерного распределения, а пропускная способность
+    /// равна Σ min(k, w)·μ·p(k, w).
+    /// # Возвращаемое значение
+    /// Характеристики СМО, тип: `Result<StationaryCharacteristics, Box<dyn Error>>`.
+    pub fn characteristics(&self) -> Result<StationaryCharacteristics, Box<dyn Error>> {
+        let distribution = self.stationary_distribution()?;
+        let (busy_channels, queue_length) = self.states().iter()
+            .zip(distribution.iter())
+            .fold((0.0, 0.0), |(busy, queue), (state, p)| (
+                busy + state.customers.min(state.working) as f64 * p,
+                queue + (state.customers - state.working).max(0) as f64 * p,
+            ));
+        let throughput = busy_channels * self.mu_rate;
+
+        Ok(StationaryCharacteristics::new(
+            self.lambda_rate,
+            self.mu_rate,
+            self.num_channels,
+            self.queue_size,
+            self.time,
+            self.customer_distribution(&distribution),
+            throughput
+        ).with_occupancy(busy_channels, queue_length))
+    }
+}